@@ -0,0 +1,76 @@
+#!/usr/bin/env bash
+
+# Compares the getdents scanner against the portable WalkParallel traversal.
+#
+# Usage: scripts/scanner_benchmark.sh [-r RUNS] [-c] DIR [ERD_ARGS...]
+#
+#   -r RUNS   Number of timed runs per scanner; the best and median are reported. Defaults to 5.
+#   -c        Drop the page, dentry, and inode caches before each run. Requires root.
+
+if [[ ! "$OSTYPE" =~ "linux-gnu" ]]; then
+  printf "Error: Script requires a linux-gnu operating system.\n"
+  exit 1
+fi
+
+runs=5
+cold=0
+
+while getopts "r:c" opt; do
+  case "$opt" in
+    r) runs="$OPTARG" ;;
+    c) cold=1 ;;
+    *) exit 1 ;;
+  esac
+done
+
+shift $((OPTIND - 1))
+
+if [[ -z "$1" ]]; then
+  printf "Error: Missing directory to traverse.\n"
+  exit 1
+fi
+
+if [[ "$cold" == 1 && $(/usr/bin/id -u) != 0 ]]; then
+  printf "Error: Dropping caches requires root privilege.\n"
+  exit 1
+fi
+
+cargo build --release || exit 1
+
+erd="$(pwd)/target/release/erd"
+
+# Prints the wall-clock time of each run in milliseconds.
+bench() {
+  local scanner="$1"
+  shift
+
+  # Warm-up run so that both scanners start from the same cache state.
+  "$erd" --no-config --scanner "$scanner" "$@" > /dev/null
+
+  for _ in $(seq "$runs"); do
+    if [[ "$cold" == 1 ]]; then
+      sync
+      echo 3 > /proc/sys/vm/drop_caches
+    fi
+
+    local start end
+    start=$(date +%s%N)
+    "$erd" --no-config --scanner "$scanner" "$@" > /dev/null
+    end=$(date +%s%N)
+
+    echo $(((end - start) / 1000000))
+  done
+}
+
+echo "Executing command: $erd --no-config --scanner <SCANNER> ${*}"
+echo
+
+printf "%-10s %10s %10s\n" "scanner" "best (ms)" "median (ms)"
+
+for scanner in walk getdents; do
+  times=$(bench "$scanner" "$@" | sort -n)
+  best=$(echo "$times" | head -n 1)
+  median=$(echo "$times" | sed -n "$(((runs + 1) / 2))p")
+
+  printf "%-10s %10s %10s\n" "$scanner" "$best" "$median"
+done
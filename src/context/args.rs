@@ -84,15 +84,35 @@ pub trait Reconciler: CommandFactory + FromArgMatches {
                         .is_some_and(|b| *b)
                     {
                         final_args.push(key);
+                    }
+                },
+                ArgAction::SetFalse => (),
+                ArgAction::Append => {
+                    let Ok(Some(raw)) = argument_source.try_get_raw(id_str) else {
+                        continue;
                     };
+                    for value in raw {
+                        final_args.push(key.clone());
+                        final_args.push(value.to_os_string());
+                    }
                 },
-                ArgAction::SetFalse => continue,
                 _ => {
                     let Ok(Some(raw)) = argument_source.try_get_raw(id_str) else {
                         continue;
                     };
                     final_args.push(key);
-                    final_args.extend(raw.map(OsStr::to_os_string));
+
+                    // Delimited values are split by the time we get them so they're rejoined in
+                    // order to be parsed as a single argument again.
+                    if let Some(delimiter) = arg.get_value_delimiter() {
+                        let joined = raw
+                            .map(OsStr::to_os_string)
+                            .collect::<Vec<_>>()
+                            .join(OsStr::new(&delimiter.to_string()));
+                        final_args.push(joined);
+                    } else {
+                        final_args.extend(raw.map(OsStr::to_os_string));
+                    }
                 },
             }
         }
@@ -1,5 +1,41 @@
-use super::{Context, PrefixKind};
-use std::convert::From;
+use super::{Context, DiskUsage, PrefixKind};
+use clap::ValueEnum;
+use std::{collections::HashMap, convert::From};
+
+/// The columns that can be displayed to the left of each file name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Kind {
+    /// Disk usage of each of the selected metrics
+    Size,
+
+    /// Permissions in symbolic or octal notation
+    #[cfg(unix)]
+    Perms,
+
+    /// Owner of the file
+    #[cfg(unix)]
+    Owner,
+
+    /// Group of the file
+    #[cfg(unix)]
+    Group,
+
+    /// Inode number
+    #[cfg(unix)]
+    Ino,
+
+    /// Number of hardlinks to the underlying inode
+    #[cfg(unix)]
+    Nlink,
+
+    /// Number of blocks allocated to the file
+    #[cfg(unix)]
+    Blocks,
+
+    /// Timestamp selected by --time
+    #[cfg(unix)]
+    Time,
+}
 
 /// Utility struct to help store maximum column widths for attributes of each node. Each width is
 /// measured as the number of columns of the tty's window.
@@ -7,6 +43,9 @@ use std::convert::From;
 pub struct Properties {
     pub max_size_width: usize,
     pub max_size_unit_width: usize,
+    pub secondary_size_widths: HashMap<DiskUsage, SizeWidths>,
+    pub max_ratio_width: usize,
+    pub max_size_values: HashMap<DiskUsage, u64>,
 
     #[cfg(unix)]
     pub max_nlink_width: usize,
@@ -22,18 +61,44 @@ pub struct Properties {
 
     #[cfg(unix)]
     pub max_group_width: usize,
+
+    #[cfg(unix)]
+    pub max_time_width: usize,
+}
+
+/// Maximum column widths of a single disk usage metric: `size` is the width of the numeric value and
+/// `unit` is the width of its unit if the metric is measured in bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SizeWidths {
+    pub size: usize,
+    pub unit: usize,
 }
 
 impl From<&Context> for Properties {
     fn from(ctx: &Context) -> Self {
-        let unit_width = match ctx.unit {
-            PrefixKind::Bin if ctx.human => 3,
-            PrefixKind::Si if ctx.human => 2,
+        let unit_width = match (ctx.unit_fixed, ctx.unit) {
+            (Some(unit), _) => unit.as_str().len(),
+            (None, PrefixKind::Bin) if ctx.human => 3,
+            (None, PrefixKind::Si) if ctx.human => 2,
             _ => 1,
         };
 
+        let secondary_size_widths = ctx
+            .secondary_disk_usages()
+            .iter()
+            .copied()
+            .map(|disk_usage| {
+                let widths = SizeWidths {
+                    size: 0,
+                    unit: unit_width,
+                };
+                (disk_usage, widths)
+            })
+            .collect();
+
         Self {
             max_size_unit_width: unit_width,
+            secondary_size_widths,
             ..Default::default()
         }
     }
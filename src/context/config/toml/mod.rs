@@ -8,6 +8,13 @@ pub mod error;
 /// Testing related to `.erdtree.toml`.
 pub mod test;
 
+/// Name of the table in `.erdtree.toml` whose entries override individual styles of the theme.
+const STYLES_TABLE: &str = "styles";
+
+/// Name of the table in `.erdtree.toml` whose sub-tables map extensions, file names, globs, and
+/// file types to icons. It can't be named `icons` as that key is taken by `--icons`.
+const ICON_TABLE: &str = "icon";
+
 /// Represents an instruction on how to handle a single key-value pair, which makes up a single
 /// command-line argument, when constructing the arguments vector.
 enum ArgInstructions {
@@ -18,6 +25,10 @@ enum ArgInstructions {
     /// Used for arguments such as `--threads 10`.
     PushKeyValue { parsed_value: OsString },
 
+    /// Used for arguments that may be specified multiple times such as `--ignore-file`. When
+    /// `ignore_file = ["a", "b"]` is set in `.erdtree.toml` the key is pushed once per value.
+    PushKeyValues { parsed_values: Vec<OsString> },
+
     /// If a bool field is set to false in `.erdtree.toml` (e.g. `icons = false`) then we want to
     /// completely omit the key-value pair from the arguments that we ultimately use.
     Pass,
@@ -27,6 +38,11 @@ enum ArgInstructions {
 /// represents command-line arguments from `.erdtree.toml`. If a `named_table` is provided then
 /// the top-level table in `.erdtree.toml` is ignored and the configurations specified in the
 /// `named_table` will be used instead.
+///
+/// Entries of a `styles` table, e.g. `[styles]` or `[<named_table>.styles]`, are each turned into
+/// a `--style KEY=STYLE` argument with the keys of nested tables joined by a `.`. Likewise entries
+/// of the sub-tables of an `icon` table, e.g. `[icon.ext]`, are each turned into an
+/// `--icon KIND:PATTERN=ICON` argument where `KIND` is the name of the sub-table.
 pub fn parse(config: Config, named_table: Option<&str>) -> Result<Vec<OsString>, Error> {
     let mut args_map = config.cache.into_table()?;
 
@@ -37,7 +53,12 @@ pub fn parse(config: Config, named_table: Option<&str>) -> Result<Vec<OsString>,
             .ok_or_else(|| Error::MissingAltConfig(table.to_owned()))?;
 
         args_map = new_conf;
-    } else {
+    }
+
+    let styles = args_map.remove(STYLES_TABLE);
+    let icon_mappings = args_map.remove(ICON_TABLE);
+
+    if named_table.is_none() {
         args_map.retain(|_k, v| !matches!(v.kind, ValueKind::Table(_)));
     }
 
@@ -53,18 +74,84 @@ pub fn parse(config: Config, named_table: Option<&str>) -> Result<Vec<OsString>,
                 parsed_args.push(parsed_value);
             },
 
+            ArgInstructions::PushKeyValues { parsed_values } => {
+                for parsed_value in parsed_values {
+                    parsed_args.push(process_key(k));
+                    parsed_args.push(parsed_value);
+                }
+            },
+
             ArgInstructions::PushKeyOnly => {
                 let fmt_key = process_key(k);
                 parsed_args.push(fmt_key);
             },
 
-            ArgInstructions::Pass => continue,
+            ArgInstructions::Pass => (),
+        }
+    }
+
+    if let Some(styles) = styles {
+        let mut overrides = vec![];
+        parse_styles("", styles, &mut overrides)?;
+        overrides.sort();
+
+        for style in overrides {
+            parsed_args.push(OsString::from("--style"));
+            parsed_args.push(OsString::from(style));
+        }
+    }
+
+    if let Some(icon_mappings) = icon_mappings {
+        let mut mappings = vec![];
+        parse_icons(icon_mappings, &mut mappings)?;
+        mappings.sort();
+
+        for mapping in mappings {
+            parsed_args.push(OsString::from("--icon"));
+            parsed_args.push(OsString::from(mapping));
         }
     }
 
     Ok(parsed_args)
 }
 
+/// Flattens the sub-tables of the `icon` table into `KIND:PATTERN=ICON` mappings.
+fn parse_icons(table: Value, mappings: &mut Vec<String>) -> Result<(), Error> {
+    for (kind, patterns) in table.into_table()? {
+        let ValueKind::Table(patterns) = patterns.kind else {
+            return Err(Error::InvalidArgument(format!("{ICON_TABLE}.{kind}")));
+        };
+
+        for (pattern, icon) in patterns {
+            let ValueKind::String(icon) = icon.kind else {
+                return Err(Error::InvalidArgument(format!(
+                    "{ICON_TABLE}.{kind}.{pattern}"
+                )));
+            };
+
+            mappings.push(format!("{kind}:{pattern}={icon}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Flattens the `styles` table into `KEY=STYLE` pairs where the keys of nested tables, such as
+/// `[styles.size]`, are prefixed by the name of the table they belong to e.g. `size.mb`.
+fn parse_styles(prefix: &str, table: Value, overrides: &mut Vec<String>) -> Result<(), Error> {
+    for (k, v) in table.into_table()? {
+        let key = format!("{prefix}{k}");
+
+        match v.kind {
+            ValueKind::Table(_) => parse_styles(&format!("{key}."), v, overrides)?,
+            ValueKind::String(spec) => overrides.push(format!("{key}={spec}")),
+            _ => return Err(Error::InvalidArgument(format!("{STYLES_TABLE}.{key}"))),
+        }
+    }
+
+    Ok(())
+}
+
 /// Reads in `.erdtree.toml` file.
 pub fn load() -> Result<Config, Error> {
     #[cfg(windows)]
@@ -125,6 +212,14 @@ fn parse_argument(keyword: &str, arg: &Value) -> Result<ArgInstructions, Error>
         ValueKind::I128(val) => try_parse_num!(*val),
         ValueKind::U64(val) => try_parse_num!(*val),
         ValueKind::U128(val) => try_parse_num!(*val),
+        ValueKind::Array(vals) => vals
+            .iter()
+            .map(|val| match parse_argument(keyword, val)? {
+                ArgInstructions::PushKeyValue { parsed_value } => Ok(parsed_value),
+                _ => Err(Error::InvalidArgument(keyword.to_owned())),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|parsed_values| ArgInstructions::PushKeyValues { parsed_values }),
         _ => Err(Error::InvalidArgument(keyword.to_owned())),
     }
 }
@@ -35,7 +35,7 @@ fn parse_toml() -> Result<(), Box<dyn std::error::Error>> {
     // TOP-LEVEL TABLE
     let mut toml = super::parse(config.clone(), None)?;
 
-    let expected = vec![
+    let expected = [
         OsString::from("--"),
         OsString::from("--icons"),
         OsString::from("--human"),
@@ -62,7 +62,7 @@ fn parse_toml() -> Result<(), Box<dyn std::error::Error>> {
     // NAMED-TABLE
     let mut toml = super::parse(config, Some("grogoroth"))?;
 
-    let expected = vec![
+    let expected = [
         OsString::from("--"),
         OsString::from("--disk-usage"),
         OsString::from("block"),
@@ -89,3 +89,130 @@ fn parse_toml() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn parse_toml_array() -> Result<(), Box<dyn std::error::Error>> {
+    use config::{Config, File, FileFormat};
+    use std::ffi::OsString;
+
+    let toml_contents = r#"
+        ignore_file = ["build.ignore", "vendor.ignore"]
+    "#;
+
+    let config = Config::builder()
+        .add_source(File::from_str(toml_contents, FileFormat::Toml))
+        .build()?;
+
+    let toml = super::parse(config, None)?;
+
+    let expected = [
+        OsString::from("--"),
+        OsString::from("--ignore-file"),
+        OsString::from("build.ignore"),
+        OsString::from("--ignore-file"),
+        OsString::from("vendor.ignore"),
+    ];
+
+    assert_eq!(toml, expected);
+
+    Ok(())
+}
+
+#[test]
+fn parse_toml_styles() -> Result<(), Box<dyn std::error::Error>> {
+    use config::{Config, File, FileFormat};
+    use std::ffi::OsString;
+
+    let toml_contents = r#"
+        theme = "nord"
+
+        [styles]
+        tree = "dimmed"
+        owner = "bold #ff8800"
+
+        [styles.size]
+        mb = "208"
+
+        [dark]
+        theme = "dracula"
+
+        [dark.styles]
+        link = "red on black"
+    "#;
+
+    let config = Config::builder()
+        .add_source(File::from_str(toml_contents, FileFormat::Toml))
+        .build()?;
+
+    // TOP-LEVEL TABLE
+    let toml = super::parse(config.clone(), None)?;
+
+    let expected = [
+        OsString::from("--"),
+        OsString::from("--theme"),
+        OsString::from("nord"),
+        OsString::from("--style"),
+        OsString::from("owner=bold #ff8800"),
+        OsString::from("--style"),
+        OsString::from("size.mb=208"),
+        OsString::from("--style"),
+        OsString::from("tree=dimmed"),
+    ];
+
+    assert_eq!(toml, expected);
+
+    // NAMED-TABLE
+    let toml = super::parse(config, Some("dark"))?;
+
+    let expected = [
+        OsString::from("--"),
+        OsString::from("--theme"),
+        OsString::from("dracula"),
+        OsString::from("--style"),
+        OsString::from("link=red on black"),
+    ];
+
+    assert_eq!(toml, expected);
+
+    Ok(())
+}
+
+#[test]
+fn parse_toml_icons() -> Result<(), Box<dyn std::error::Error>> {
+    use config::{Config, File, FileFormat};
+    use std::ffi::OsString;
+
+    let toml_contents = r#"
+        icons = true
+
+        [icon.ext]
+        rs = "R bold 208"
+
+        [icon.name]
+        "Cargo.lock" = "C"
+
+        [icon.glob]
+        "*.test.js" = "T yellow"
+    "#;
+
+    let config = Config::builder()
+        .add_source(File::from_str(toml_contents, FileFormat::Toml))
+        .build()?;
+
+    let toml = super::parse(config, None)?;
+
+    let expected = [
+        OsString::from("--"),
+        OsString::from("--icons"),
+        OsString::from("--icon"),
+        OsString::from("ext:rs=R bold 208"),
+        OsString::from("--icon"),
+        OsString::from("glob:*.test.js=T yellow"),
+        OsString::from("--icon"),
+        OsString::from("name:Cargo.lock=C"),
+    ];
+
+    assert_eq!(toml, expected);
+
+    Ok(())
+}
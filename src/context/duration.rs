@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+/// Parses a [Duration] made up of a possibly fractional number followed by one of the units `ms`,
+/// `s`, `m`, or `h`, e.g. `500ms` or `1.5m`. A number without a unit is in seconds.
+pub fn parse(arg: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration '{arg}'; expected e.g. 500ms, 10s, 1.5m, or 2h");
+
+    let split = arg
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(arg.len());
+
+    let (value, unit) = arg.split_at(split);
+
+    let secs_per_unit = match unit {
+        "ms" => 0.001,
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        "h" => 3600.0,
+        _ => return Err(invalid()),
+    };
+
+    let value = value.parse::<f64>().map_err(|_| invalid())?;
+
+    Duration::try_from_secs_f64(value * secs_per_unit).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse;
+    use std::time::Duration;
+
+    #[test]
+    fn parse_durations() {
+        assert_eq!(parse("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse("1.5m"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("2h"), Ok(Duration::from_secs(7200)));
+        assert_eq!(parse("0s"), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn parse_invalid_durations() {
+        for arg in ["", "s", "10d", "1.2.3s", "-5s", "5 s"] {
+            assert!(parse(arg).is_err(), "{arg}");
+        }
+    }
+}
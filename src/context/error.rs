@@ -23,11 +23,20 @@ pub enum Error {
     #[error("Missing '--pattern' argument")]
     PatternNotProvided,
 
+    #[error("'--sort-metric {0}' must be one of the metrics provided to '--disk-usage'")]
+    SortMetricNotDisplayed(String),
+
+    #[error("'{0}' requires '--long' or '--columns' with a column other than 'size'")]
+    RequiresLongView(&'static str),
+
+    #[error("No such user '{0}'")]
+    UnknownUser(String),
+
     #[error("{0}")]
-    ConfigError(#[from] TomlError),
+    Toml(#[from] TomlError),
 
     #[error("{0}")]
-    MatchError(#[from] MatchesError),
+    Matches(#[from] MatchesError),
 
     #[error("'--config' was specified but a `.erdtree.toml` file could not be found")]
     NoToml,
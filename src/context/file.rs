@@ -1,6 +1,7 @@
 use clap::ValueEnum;
+use ignore::DirEntry;
 
-/// File-types found in both Unix and Windows.
+/// File-types to filter on. Sockets, FIFOs, devices, and executables are specific to Unix.
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum Type {
     /// A regular file.
@@ -12,4 +13,62 @@ pub enum Type {
 
     /// A symlink.
     Link,
+
+    /// A symlink whose target doesn't exist.
+    BrokenLink,
+
+    /// A regular file that is executable by its owner, group, or others.
+    #[cfg(unix)]
+    Executable,
+
+    /// A Unix domain socket.
+    #[cfg(unix)]
+    Socket,
+
+    /// A named pipe.
+    #[cfg(unix)]
+    Fifo,
+
+    /// A block device.
+    #[cfg(unix)]
+    BlockDevice,
+
+    /// A character device.
+    #[cfg(unix)]
+    CharDevice,
+}
+
+impl Type {
+    /// Whether or not `dir_entry` is of this file-type. Only executables require the metadata of
+    /// `dir_entry` to be queried.
+    pub fn matches(self, dir_entry: &DirEntry) -> bool {
+        #[cfg(unix)]
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+        let Some(ft) = dir_entry.file_type() else {
+            return false;
+        };
+
+        match self {
+            Self::File => ft.is_file(),
+            Self::Dir => ft.is_dir(),
+            Self::Link => ft.is_symlink(),
+            Self::BrokenLink => ft.is_symlink() && !dir_entry.path().exists(),
+            #[cfg(unix)]
+            Self::Executable => {
+                ft.is_file()
+                    && dir_entry
+                        .metadata()
+                        .is_ok_and(|md| md.permissions().mode() & 0o111 != 0)
+            },
+            #[cfg(unix)]
+            Self::Socket => ft.is_socket(),
+            #[cfg(unix)]
+            Self::Fifo => ft.is_fifo(),
+            #[cfg(unix)]
+            Self::BlockDevice => ft.is_block_device(),
+            #[cfg(unix)]
+            Self::CharDevice => ft.is_char_device(),
+        }
+    }
 }
@@ -1,7 +1,17 @@
-use super::disk_usage::{file_size::DiskUsage, units::PrefixKind};
+use super::disk_usage::{
+    file_size::DiskUsage,
+    units::{FixedUnit, PrefixKind},
+};
+#[cfg(unix)]
+use super::fs::permissions::filter::ModeFilter;
+use super::icons::custom;
+use super::styles::{
+    branches::Branches,
+    palette::{self, Preset},
+};
 
 use args::Reconciler;
-use clap::{FromArgMatches, Parser};
+use clap::{builder::ArgAction, FromArgMatches, Parser, ValueEnum};
 use color::Coloring;
 use error::Error;
 use ignore::{
@@ -11,11 +21,14 @@ use ignore::{
 use regex::Regex;
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     convert::From,
     io::{stdin, stdout, IsTerminal},
     num::NonZeroUsize,
     path::{Path, PathBuf},
+    sync::OnceLock,
     thread::available_parallelism,
+    time::Duration,
 };
 
 /// Concerned with figuring out how to reconcile arguments provided via the command-line with
@@ -31,6 +44,9 @@ pub mod color;
 /// Controlling order of directories in output.
 pub mod dir;
 
+/// Parsing of durations such as `--timeout`.
+pub mod duration;
+
 /// [Context] related errors.
 pub mod error;
 
@@ -43,6 +59,9 @@ pub mod layout;
 /// Utilities to print output.
 pub mod column;
 
+/// How directories are read during traversal.
+pub mod scanner;
+
 /// Printing order kinds.
 pub mod sort;
 
@@ -68,26 +87,66 @@ pub struct Context {
     #[arg(short = 'C', long, value_enum, default_value_t)]
     pub color: Coloring,
 
-    /// Print physical or logical file size
-    #[arg(short, long, value_enum, default_value_t)]
-    pub disk_usage: DiskUsage,
+    /// Color timestamps by age and disk usage by size on a gradient rather than a fixed theme
+    #[arg(long)]
+    pub gradient: bool,
+
+    /// Built-in color theme to use as the base for any style overrides
+    #[arg(long, value_enum, default_value_t)]
+    pub theme: Preset,
+
+    /// Override a single style of the theme e.g. 'size.mb=bold #ff8800'; may be specified multiple times
+    #[arg(long, value_name = "KEY=STYLE", value_parser = palette::parse_override)]
+    pub style: Vec<palette::Override>,
+
+    /// Characters used to draw the branches of the tree
+    #[arg(long, value_enum, default_value_t)]
+    pub branches: Branches,
+
+    /// Print physical or logical file size; multiple comma-separated metrics are shown in order
+    #[arg(short, long, value_enum, value_delimiter = ',', default_value = "physical", action = ArgAction::Set)]
+    disk_usage: Vec<DiskUsage>,
+
+    /// Which disk usage metric to sort by and total directories with; one of --disk-usage, the first by default
+    #[arg(long, value_enum, value_name = "DISK_USAGE")]
+    sort_metric: Option<DiskUsage>,
+
+    /// Show the ratio of physical to logical size, highlighting sparse and compressed files
+    #[arg(long)]
+    pub ratio: bool,
+
+    /// Show a per-language summary of source lines of code beneath the output
+    #[arg(long)]
+    pub sloc_summary: bool,
 
     /// Follow symlinks
     #[arg(short = 'f', long)]
     pub follow: bool,
 
+    /// Show the full resolution chain of symlinks, flagging cycles and targets outside the root
+    #[arg(long)]
+    pub link_chain: bool,
+
     /// Print disk usage in human-readable format
     #[arg(short = 'H', long)]
     pub human: bool,
 
-    /// Do not respect .gitignore files
+    /// Do not respect .gitignore and .erdignore files
     #[arg(short = 'i', long)]
     pub no_ignore: bool,
 
+    /// Additional gitignore-style file of patterns to exclude; may be specified multiple times
+    #[arg(long, value_name = "PATH")]
+    pub ignore_file: Vec<PathBuf>,
+
     /// Display file icons
     #[arg(short = 'I', long)]
     pub icons: bool,
 
+    /// Add or override an icon by ext, name, glob, or type e.g. 'ext:rs=ICON 208'; may be specified multiple times
+    #[arg(long, value_name = "KIND:PATTERN=ICON", value_parser = custom::parse_mapping)]
+    pub icon: Vec<custom::Mapping>,
+
     /// Show extended metadata and attributes
     #[cfg(unix)]
     #[arg(short, long)]
@@ -108,20 +167,54 @@ pub struct Context {
     #[arg(long)]
     pub nlink: bool,
 
+    /// Flag setuid, setgid, sticky, and world-writable files as well as files with capabilities
+    #[cfg(unix)]
+    #[arg(long)]
+    pub audit: bool,
+
+    /// Only show files flagged by --audit along with the directories that contain them
+    #[cfg(unix)]
+    #[arg(long, requires = "audit")]
+    pub audit_only: bool,
+
+    /// List extended attributes after each file name, decoding POSIX ACLs and security contexts
+    #[cfg(unix)]
+    #[arg(long)]
+    pub xattrs: bool,
+
+    /// Also show the values of extended attributes, truncated to NUM bytes
+    #[cfg(unix)]
+    #[arg(long, value_name = "NUM", requires = "xattrs")]
+    pub xattr_values: Option<usize>,
+
+    /// Comma-separated columns to show in order; overrides --long, --group, --ino, and --nlink
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "COLUMNS", action = ArgAction::Set)]
+    columns: Option<Vec<column::Kind>>,
+
+    /// Show numeric user and group ids instead of names
+    #[cfg(unix)]
+    #[arg(long)]
+    pub numeric_ids: bool,
+
     /// Show permissions in numeric octal format instead of symbolic
     #[cfg(unix)]
-    #[arg(long, requires = "long")]
+    #[arg(long)]
     pub octal: bool,
 
-    /// Which kind of timestamp to use; modified by default
+    /// Which kinds of timestamps to show as comma-separated columns; modified by default
     #[cfg(unix)]
-    #[arg(long, value_enum, requires = "long")]
-    pub time: Option<time::Stamp>,
+    #[arg(long, value_enum, value_delimiter = ',', action = ArgAction::Set)]
+    time: Vec<time::Stamp>,
 
     /// Which format to use for the timestamp; default by default
     #[cfg(unix)]
-    #[arg(long = "time-format", value_enum, requires = "long")]
-    pub time_format: Option<time::Format>,
+    #[arg(long = "time-format", value_parser = time::FormatParser)]
+    time_format: Option<time::Format>,
+
+    /// Which time zone to display timestamps in; local by default
+    #[cfg(unix)]
+    #[arg(long = "time-zone", value_parser = time::ZoneParser)]
+    time_zone: Option<time::Zone>,
 
     /// Maximum depth to display
     #[arg(short = 'L', long, value_name = "NUM")]
@@ -143,6 +236,27 @@ pub struct Context {
     #[arg(short = 't', long, requires = "pattern", value_enum)]
     pub file_type: Option<file::Type>,
 
+    /// Only show files whose permissions match MODE, either octal e.g. 755 or symbolic e.g. o+w;
+    /// prefix with '-' to require at least those bits or '/' to require any of them
+    #[cfg(unix)]
+    #[arg(long, value_name = "MODE", allow_hyphen_values = true)]
+    pub perm: Option<ModeFilter>,
+
+    /// Only show files that the current user may execute
+    #[cfg(unix)]
+    #[arg(long)]
+    pub executable: bool,
+
+    /// Only show files that USER, given by name or id, may write to
+    #[cfg(unix)]
+    #[arg(long, value_name = "USER")]
+    pub writable_by: Option<String>,
+
+    /// Only show files whose owner no longer exists, such as after an account is removed
+    #[cfg(unix)]
+    #[arg(long)]
+    pub no_user: bool,
+
     /// Remove empty directories from output
     #[arg(short = 'P', long)]
     pub prune: bool,
@@ -159,10 +273,26 @@ pub struct Context {
     #[arg(short = 'T', long, default_value_t = Context::num_threads())]
     pub threads: usize,
 
+    /// How to read directories; falls back to walk where getdents isn't supported
+    #[arg(long, value_enum, default_value_t)]
+    pub scanner: scanner::Scanner,
+
+    /// Stop traversal after DURATION, e.g. 500ms, 10s, or 2m, and show what was found until then
+    #[arg(long, value_name = "DURATION", value_parser = duration::parse)]
+    pub timeout: Option<Duration>,
+
     /// Report disk usage in binary or SI units
     #[arg(short, long, value_enum, default_value_t)]
     pub unit: PrefixKind,
 
+    /// Report disk usage in a fixed unit regardless of magnitude; implies human-readable format
+    #[arg(long, value_enum, ignore_case = true, value_name = "UNIT")]
+    pub unit_fixed: Option<FixedUnit>,
+
+    /// Number of decimal places to show for human-readable disk usage; 1 by default
+    #[arg(long, value_name = "NUM")]
+    precision: Option<usize>,
+
     /// Prevent traversal into directories that are on different filesystems
     #[arg(short = 'x', long = "one-file-system")]
     pub same_fs: bool,
@@ -218,10 +348,22 @@ pub struct Context {
     #[clap(skip = usize::default())]
     pub max_size_width: usize,
 
-    /// Restricts column width of disk_usage units
+    /// Restricts column width of `disk_usage` units
     #[clap(skip = usize::default())]
     pub max_size_unit_width: usize,
 
+    /// Restricts column widths of disk usage metrics other than the primary one
+    #[clap(skip)]
+    pub max_secondary_size_widths: HashMap<DiskUsage, column::SizeWidths>,
+
+    /// Restricts column width of the physical to logical size ratio
+    #[clap(skip = usize::default())]
+    pub max_ratio_width: usize,
+
+    /// Largest value of each disk usage metric which marks the far end of the size gradient
+    #[clap(skip)]
+    pub max_size_values: HashMap<DiskUsage, u64>,
+
     /// Restricts column width of nlink for long view
     #[clap(skip = usize::default())]
     #[cfg(unix)]
@@ -247,21 +389,60 @@ pub struct Context {
     #[cfg(unix)]
     pub max_group_width: usize,
 
+    /// Restricts column width of timestamps for long view if they're in a custom format
+    #[clap(skip = usize::default())]
+    #[cfg(unix)]
+    pub max_time_width: usize,
+
     /// Width of the terminal emulator's window
     #[clap(skip)]
     pub window_width: Option<usize>,
+
+    /// Canonical path of the root directory which is computed once as it's needed for every file
+    #[clap(skip)]
+    dir_canonical: OnceLock<PathBuf>,
+
+    /// Disk usage metrics computed in addition to the primary one which are needed for every file
+    #[clap(skip)]
+    secondary_disk_usages: OnceLock<Vec<DiskUsage>>,
+
+    /// Columns to the left of each file name which are needed for every row
+    #[clap(skip)]
+    displayed_columns: OnceLock<Vec<column::Kind>>,
 }
 
 type Predicate = Result<Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static>, Error>;
 
+/// Tests the metadata of a file against the filters of the [Context].
+pub type MetadataMatcher = Box<dyn Fn(&std::fs::Metadata) -> bool + Send + Sync + 'static>;
+
 impl Context {
     /// Initializes [Context], optionally reading in the configuration file to override defaults.
     /// Arguments provided will take precedence over config.
     pub fn try_init() -> Result<Self, Error> {
-        Self::compute_args().and_then(|args| {
+        let ctx = Self::compute_args().and_then(|args| {
             color::no_color_env();
             Self::from_arg_matches(&args).map_err(Error::Config)
-        })
+        })?;
+
+        if let Some(sort_metric) = ctx.sort_metric {
+            if !ctx.disk_usage.contains(&sort_metric) {
+                let name = sort_metric
+                    .to_possible_value()
+                    .map_or_else(String::new, |value| value.get_name().to_owned());
+
+                return Err(Error::SortMetricNotDisplayed(name));
+            }
+        }
+
+        #[cfg(unix)]
+        if !ctx.long_view() {
+            if let Some(arg) = ctx.long_view_arg() {
+                return Err(Error::RequiresLongView(arg));
+            }
+        }
+
+        Ok(ctx)
     }
 
     /// Determines whether or not it's appropriate to display color in output based on
@@ -288,8 +469,10 @@ impl Context {
     }
 
     /// Returns canonical [Path] of the root directory to be traversed.
-    pub fn dir_canonical(&self) -> PathBuf {
-        std::fs::canonicalize(self.dir()).unwrap_or_else(|_| self.dir().to_path_buf())
+    pub fn dir_canonical(&self) -> &Path {
+        self.dir_canonical.get_or_init(|| {
+            std::fs::canonicalize(self.dir()).unwrap_or_else(|_| self.dir().to_path_buf())
+        })
     }
 
     /// The max depth to print. Note that all directories are fully traversed to compute file
@@ -298,16 +481,165 @@ impl Context {
         self.level.unwrap_or(usize::MAX)
     }
 
-    /// Which timestamp type to use for long view; defaults to modified.
+    /// The primary disk usage metric which is used to sort entries by size and to prune empty
+    /// directories. Defaults to the first metric provided to `--disk-usage`.
+    pub fn disk_usage(&self) -> DiskUsage {
+        self.sort_metric
+            .or_else(|| self.disk_usage.first().copied())
+            .unwrap_or_default()
+    }
+
+    /// All of the disk usage metrics to display in the order that they should be displayed.
+    pub fn disk_usages(&self) -> &[DiskUsage] {
+        &self.disk_usage
+    }
+
+    /// Disk usage metrics that need to be computed in addition to the primary metric. This
+    /// includes the other displayed metrics as well as the metrics required to compute the physical
+    /// to logical size ratio and the source lines of code required by the per-language summary.
+    pub fn secondary_disk_usages(&self) -> &[DiskUsage] {
+        self.secondary_disk_usages.get_or_init(|| {
+            let primary = self.disk_usage();
+
+            let ratio_metrics = if self.ratio {
+                &[DiskUsage::Logical, DiskUsage::Physical][..]
+            } else {
+                &[]
+            };
+
+            let sloc_metrics = if self.sloc_summary {
+                &[DiskUsage::Sloc][..]
+            } else {
+                &[]
+            };
+
+            let mut secondaries = vec![];
+
+            for disk_usage in self
+                .disk_usage
+                .iter()
+                .chain(ratio_metrics)
+                .chain(sloc_metrics)
+            {
+                if *disk_usage != primary && !secondaries.contains(disk_usage) {
+                    secondaries.push(*disk_usage);
+                }
+            }
+
+            secondaries
+        })
+    }
+
+    /// Maximum column widths of the provided disk usage metric.
+    pub fn size_widths(&self, disk_usage: DiskUsage) -> column::SizeWidths {
+        if disk_usage == self.disk_usage() {
+            return column::SizeWidths {
+                size: self.max_size_width,
+                unit: self.max_size_unit_width,
+            };
+        }
+
+        self.max_secondary_size_widths
+            .get(&disk_usage)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Number of decimal places to show for human-readable disk usage.
+    pub fn precision(&self) -> usize {
+        self.precision.unwrap_or(1)
+    }
+
+    /// Answers whether disk usage is to be reported in a human-readable format, which is always the
+    /// case if a fixed unit is provided.
+    pub const fn human_readable(&self) -> bool {
+        self.human || self.unit_fixed.is_some()
+    }
+
+    /// The columns to display to the left of each file name in order. Unless explicitly provided,
+    /// disk usage is preceded by the attributes of the long view if it is enabled.
+    pub fn columns(&self) -> &[column::Kind] {
+        self.displayed_columns.get_or_init(|| {
+            if let Some(ref columns) = self.columns {
+                return columns.clone();
+            }
+
+            let mut columns = vec![];
+
+            #[cfg(unix)]
+            if self.long {
+                if self.ino {
+                    columns.push(column::Kind::Ino);
+                }
+
+                columns.push(column::Kind::Perms);
+
+                if self.nlink {
+                    columns.push(column::Kind::Nlink);
+                }
+
+                columns.push(column::Kind::Owner);
+
+                if self.group {
+                    columns.push(column::Kind::Group);
+                }
+
+                columns.push(column::Kind::Time);
+            }
+
+            columns.push(column::Kind::Size);
+
+            columns
+        })
+    }
+
+    /// Answers whether the columns to display were provided via `--columns`.
+    pub const fn provides_columns(&self) -> bool {
+        self.columns.is_some()
+    }
+
+    /// Answers whether any column other than disk usage is displayed, in which case the attributes
+    /// of the long view need to be gathered.
+    #[cfg(unix)]
+    pub fn long_view(&self) -> bool {
+        self.columns()
+            .iter()
+            .any(|&column| column != column::Kind::Size)
+    }
+
+    /// The first of the arguments provided that only apply to the long view, if any.
+    #[cfg(unix)]
+    fn long_view_arg(&self) -> Option<&'static str> {
+        [
+            ("--numeric-ids", self.numeric_ids),
+            ("--octal", self.octal),
+            ("--time", !self.time.is_empty()),
+            ("--time-format", self.time_format.is_some()),
+            ("--time-zone", self.time_zone.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(arg, provided)| provided.then_some(arg))
+    }
+
+    /// Which timestamp types to show in order for long view; defaults to modified.
     #[cfg(unix)]
-    pub fn time(&self) -> time::Stamp {
-        self.time.unwrap_or_default()
+    pub fn times(&self) -> &[time::Stamp] {
+        if self.time.is_empty() {
+            return &[time::Stamp::Mod];
+        }
+        &self.time
     }
 
     /// Which format to use for the timestamp; default by default
     #[cfg(unix)]
-    pub fn time_format(&self) -> time::Format {
-        self.time_format.unwrap_or_default()
+    pub fn time_format(&self) -> &time::Format {
+        self.time_format.as_ref().unwrap_or(&time::Format::Default)
+    }
+
+    /// Which time zone to display timestamps in; local by default
+    #[cfg(unix)]
+    pub fn time_zone(&self) -> time::Zone {
+        self.time_zone.unwrap_or_default()
     }
 
     /// Which `FileType` to filter on; defaults to regular file.
@@ -331,7 +663,7 @@ impl Context {
 
         Ok(match file_type {
             file::Type::Dir => Box::new(move |dir_entry| {
-                let is_dir = dir_entry.file_type().map_or(false, |ft| ft.is_dir());
+                let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
                 if is_dir {
                     return Self::ancestor_regex_match(dir_entry.path(), &re, 0);
                 }
@@ -341,20 +673,14 @@ impl Context {
 
             _ => Box::new(move |dir_entry| {
                 let entry_type = dir_entry.file_type();
-                let is_dir = entry_type.map_or(false, |ft| ft.is_dir());
+                let is_dir = entry_type.is_some_and(|ft| ft.is_dir());
 
                 if is_dir {
                     return true;
                 }
 
-                match file_type {
-                    file::Type::File if entry_type.map_or(true, |ft| !ft.is_file()) => {
-                        return false
-                    },
-                    file::Type::Link if entry_type.map_or(true, |ft| !ft.is_symlink()) => {
-                        return false
-                    },
-                    _ => {},
+                if !file_type.matches(dir_entry) {
+                    return false;
                 }
                 let file_name = dir_entry.file_name().to_string_lossy();
                 re.is_match(&file_name)
@@ -362,6 +688,125 @@ impl Context {
         })
     }
 
+    /// Returns `true` if files are filtered by `--perm`, `--executable`, `--writable-by`, or
+    /// `--no-user`.
+    pub const fn filters_metadata(&self) -> bool {
+        #[cfg(unix)]
+        return self.perm.is_some()
+            || self.executable
+            || self.writable_by.is_some()
+            || self.no_user;
+
+        #[cfg(not(unix))]
+        return false;
+    }
+
+    /// Returns `true` if directories left without any entries after filtering are removed, which
+    /// is the case with `--prune` and whenever entries are filtered.
+    pub const fn prunes(&self) -> bool {
+        self.prune || self.pattern.is_some() || self.filters_metadata()
+    }
+
+    /// Returns `true` if directories are to be read with `getdents64(2)` and files queried with
+    /// `statx(2)`. Following symlinks and filtering entries by `--pattern` or by their metadata
+    /// aren't supported, in which case the portable traversal is used instead.
+    pub const fn scans_with_getdents(&self) -> bool {
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        return matches!(self.scanner, scanner::Scanner::Getdents)
+            && !self.follow
+            && self.pattern.is_none()
+            && !self.filters_metadata();
+
+        #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+        return false;
+    }
+
+    /// Returns `true` if entries beyond `--level` can be folded into the sizes of their displayed
+    /// ancestors during traversal rather than kept in the tree. This isn't possible when whether
+    /// a directory is shown depends on what lies beneath it.
+    pub const fn folds_beyond_level(&self) -> bool {
+        #[cfg(unix)]
+        let audit_only = self.audit_only;
+
+        #[cfg(not(unix))]
+        let audit_only = false;
+
+        self.level.is_some() && !self.prunes() && !audit_only
+    }
+
+    /// Predicate used for filtering via permissions and ownership. As with [`Context::regex_predicate`],
+    /// directories are always included so that matched files may be bridged back to the root
+    /// node; whether directories match in their own right is up to [`Context::metadata_matcher`].
+    /// Symlinks that aren't followed are never matched as their permissions are meaningless.
+    #[cfg(unix)]
+    pub fn metadata_predicate(&self) -> Predicate {
+        let matches = self.metadata_matcher()?;
+
+        Ok(Box::new(move |dir_entry| {
+            let Some(ft) = dir_entry.file_type() else {
+                return false;
+            };
+
+            if ft.is_dir() {
+                return true;
+            }
+
+            if ft.is_symlink() {
+                return false;
+            }
+
+            dir_entry.metadata().is_ok_and(|md| matches(&md))
+        }))
+    }
+
+    /// Tests the permissions and ownership of a file against `--perm`, `--executable`,
+    /// `--writable-by`, and `--no-user`. Directories never match `--executable` as their execute
+    /// bits only permit searching them.
+    #[cfg(unix)]
+    pub fn metadata_matcher(&self) -> Result<MetadataMatcher, Error> {
+        use crate::fs::{
+            permissions::{class::Access, SymbolicNotation},
+            ug::{self, Account},
+        };
+        use std::os::unix::fs::MetadataExt;
+
+        let perm = self.perm;
+        let no_user = self.no_user;
+
+        let executor = self.executable.then(Account::current);
+
+        let writer = self
+            .writable_by
+            .as_deref()
+            .map(|user| Account::lookup(user).ok_or_else(|| Error::UnknownUser(user.to_owned())))
+            .transpose()?;
+
+        Ok(Box::new(move |md| {
+            let Ok(mode) = md.permissions().try_mode_symbolic_notation() else {
+                return false;
+            };
+
+            let (uid, gid) = (md.uid(), md.gid());
+
+            // Owners that couldn't be looked up aren't known to be missing.
+            if no_user && !matches!(ug::try_user_name(uid), Ok(None)) {
+                return false;
+            }
+
+            if md.is_dir() && executor.is_some() {
+                return false;
+            }
+
+            perm.map_or(true, |perm| perm.matches(&mode))
+                && executor.as_ref().map_or(true, |account| {
+                    mode.grants(Access::Execute, account, uid, gid)
+                })
+                && writer.as_ref().map_or(true, |account| {
+                    mode.grants(Access::Write, account, uid, gid)
+                })
+        }))
+    }
+
     /// Predicate used for filtering via globs and file-types.
     pub fn glob_predicate(&self) -> Predicate {
         let mut builder = OverrideBuilder::new(self.dir());
@@ -391,7 +836,7 @@ impl Context {
 
         match file_type {
             file::Type::Dir => Ok(Box::new(move |dir_entry| {
-                let is_dir = dir_entry.file_type().map_or(false, |ft| ft.is_dir());
+                let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
 
                 if is_dir {
                     if negated_glob {
@@ -410,20 +855,14 @@ impl Context {
 
             _ => Ok(Box::new(move |dir_entry| {
                 let entry_type = dir_entry.file_type();
-                let is_dir = entry_type.map_or(false, |ft| ft.is_dir());
+                let is_dir = entry_type.is_some_and(|ft| ft.is_dir());
 
                 if is_dir {
                     return true;
                 }
 
-                match file_type {
-                    file::Type::File if entry_type.map_or(true, |ft| !ft.is_file()) => {
-                        return false
-                    },
-                    file::Type::Link if entry_type.map_or(true, |ft| !ft.is_symlink()) => {
-                        return false
-                    },
-                    _ => {},
+                if !file_type.matches(dir_entry) {
+                    return false;
                 }
 
                 let matched = overrides.matched(dir_entry.path(), false);
@@ -452,6 +891,10 @@ impl Context {
     pub fn update_column_properties(&mut self, col_props: &column::Properties) {
         self.max_size_width = col_props.max_size_width;
         self.max_size_unit_width = col_props.max_size_unit_width;
+        self.max_secondary_size_widths
+            .clone_from(&col_props.secondary_size_widths);
+        self.max_ratio_width = col_props.max_ratio_width;
+        self.max_size_values.clone_from(&col_props.max_size_values);
 
         #[cfg(unix)]
         {
@@ -460,6 +903,7 @@ impl Context {
             self.max_nlink_width = col_props.max_nlink_width;
             self.max_block_width = col_props.max_block_width;
             self.max_ino_width = col_props.max_ino_width;
+            self.max_time_width = col_props.max_time_width;
         }
     }
 
@@ -469,9 +913,9 @@ impl Context {
         self.window_width = crate::tty::get_window_width();
     }
 
-    /// Answers whether disk usage is asked to be reported in bytes.
-    pub const fn byte_metric(&self) -> bool {
-        matches!(self.disk_usage, DiskUsage::Logical | DiskUsage::Physical)
+    /// Answers whether disk usage of the primary metric is asked to be reported in bytes.
+    pub fn byte_metric(&self) -> bool {
+        self.disk_usage().is_byte_metric()
     }
 
     /// Do any of the components of a path match the provided glob? This is used for ensuring that
@@ -495,6 +939,6 @@ impl Context {
 
     /// The default number of threads to use for disk-reads and parallel processing.
     fn num_threads() -> usize {
-        available_parallelism().map(NonZeroUsize::get).unwrap_or(3)
+        available_parallelism().map_or(3, NonZeroUsize::get)
     }
 }
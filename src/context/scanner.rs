@@ -0,0 +1,12 @@
+use clap::ValueEnum;
+
+/// How directories are read and how files are queried during traversal.
+#[derive(Clone, Copy, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Scanner {
+    /// Portable traversal that queries all of the metadata of every file
+    #[default]
+    Walk,
+
+    /// Read directories with getdents64 and query only what's needed with statx; Linux only
+    Getdents,
+}
@@ -39,3 +39,13 @@ pub enum Type {
     #[value(alias("rmtime"))]
     Rmod,
 }
+
+impl Type {
+    /// Whether or not entries are ordered by one of their timestamps.
+    pub const fn is_by_time(self) -> bool {
+        matches!(
+            self,
+            Self::Access | Self::Raccess | Self::Create | Self::Rcreate | Self::Mod | Self::Rmod
+        )
+    }
+}
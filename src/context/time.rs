@@ -1,12 +1,24 @@
-use clap::ValueEnum;
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, FixedOffset, Local, Offset, Utc,
+};
+use clap::{
+    builder::{EnumValueParser, PossibleValue, TypedValueParser},
+    error::{Error, ErrorKind},
+    Arg, Command, ValueEnum,
+};
+use std::{ffi::OsStr, iter, time::SystemTime};
 
 /// Different types of timestamps available in long-view.
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum Stamp {
-    /// Time created (alias: ctime)
-    #[value(alias("ctime"))]
+    /// Time created (alias: btime, ctime)
+    #[value(alias("btime"), alias("ctime"))]
     Create,
 
+    /// Time of last status change i.e. inode change
+    Change,
+
     /// Time last accessed (alias: atime)
     #[value(alias("atime"))]
     Access,
@@ -18,7 +30,7 @@ pub enum Stamp {
 }
 
 /// Different formatting options for timestamps
-#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum Format {
     /// Timestamp formatted following the iso8601, with slight differences and the time-zone omitted
     Iso,
@@ -29,7 +41,255 @@ pub enum Format {
     /// Timestamp only shows date without time in YYYY-MM-DD format
     Short,
 
+    /// Timestamp is shown relative to the current time e.g. 3 days ago
+    Relative,
+
     /// Timestamp is shown in DD MMM HH:MM format
     #[default]
     Default,
+
+    /// Timestamp is shown using the provided strftime format string
+    #[value(skip)]
+    Custom(String),
+}
+
+/// Time zone in which timestamps are displayed.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Zone {
+    /// The system's local time zone
+    #[default]
+    Local,
+
+    /// Coordinated Universal Time
+    Utc,
+
+    /// A fixed offset from UTC
+    #[value(skip)]
+    Offset(FixedOffset),
+}
+
+/// Prefix used to provide a custom strftime format string to `--time-format`.
+const CUSTOM_FORMAT_PREFIX: &str = "custom:";
+
+impl Format {
+    /// The minimum width of a formatted timestamp so that timestamps are right-aligned. Custom
+    /// formats don't have a fixed width so the longest timestamp is measured instead.
+    pub const fn width(&self) -> Option<usize> {
+        match self {
+            Self::Relative => Some(14),
+            Self::Custom(_) => None,
+            _ => Some(12),
+        }
+    }
+
+    /// Formats `time` in the provided time `zone`.
+    pub fn format(&self, time: SystemTime, zone: Zone) -> String {
+        if matches!(self, Self::Relative) {
+            return relative(Utc::now(), DateTime::<Utc>::from(time));
+        }
+
+        let dt = zone.datetime(time);
+
+        let delayed_format = match self {
+            Self::Default | Self::Relative => dt.format("%d %h %H:%M %g"),
+            Self::Iso => dt.format("%Y-%m-%d %H:%M:%S"),
+            Self::IsoStrict => dt.format("%Y-%m-%dT%H:%M:%S%Z"),
+            Self::Short => dt.format("%Y-%m-%d"),
+            Self::Custom(fmt) => dt.format(fmt),
+        };
+
+        format!("{delayed_format}")
+    }
+}
+
+impl Zone {
+    /// Converts `time` into a [`DateTime`] in this time zone.
+    pub fn datetime(self, time: SystemTime) -> DateTime<FixedOffset> {
+        match self {
+            Self::Local => {
+                let dt = DateTime::<Local>::from(time);
+                dt.with_timezone(&dt.offset().fix())
+            },
+            Self::Utc => DateTime::<Utc>::from(time).with_timezone(&Utc.fix()),
+            Self::Offset(offset) => DateTime::<Utc>::from(time).with_timezone(&offset),
+        }
+    }
+}
+
+/// Describes how far `then` is from `now` in the largest whole unit of time, e.g. "3 days ago".
+pub fn relative(now: DateTime<Utc>, then: DateTime<Utc>) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    const UNITS: [(u64, &str); 6] = [
+        (365 * DAY, "year"),
+        (30 * DAY, "month"),
+        (7 * DAY, "week"),
+        (DAY, "day"),
+        (HOUR, "hour"),
+        (MINUTE, "minute"),
+    ];
+
+    let secs = now.signed_duration_since(then).num_seconds();
+    let elapsed = secs.unsigned_abs();
+
+    let Some((amount, unit)) = UNITS.iter().find_map(|(unit_secs, unit)| {
+        (elapsed >= *unit_secs).then_some((elapsed / unit_secs, *unit))
+    }) else {
+        return String::from("just now");
+    };
+
+    let plural = if amount == 1 { "" } else { "s" };
+
+    if secs < 0 {
+        format!("in {amount} {unit}{plural}")
+    } else {
+        format!("{amount} {unit}{plural} ago")
+    }
+}
+
+/// How old `time` is on a logarithmic scale from `0.0`, a minute old or less, to `1.0`, ten years
+/// old or more. Timestamps in the future are considered brand new.
+pub fn age_fraction(time: SystemTime) -> f64 {
+    const MINUTE: f64 = 60.0;
+    const TEN_YEARS: f64 = 10.0 * 365.0 * 24.0 * 60.0 * MINUTE;
+
+    let age = SystemTime::now()
+        .duration_since(time)
+        .map_or(0.0, |age| age.as_secs_f64());
+
+    (age.max(MINUTE) / MINUTE).log(TEN_YEARS / MINUTE).min(1.0)
+}
+
+/// Parses `--time-format` which is either one of the variants of [`Format`] or a strftime format
+/// string prefixed with `custom:`.
+#[derive(Clone)]
+pub struct FormatParser;
+
+impl TypedValueParser for FormatParser {
+    type Value = Format;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Format, Error> {
+        let Some(fmt) = value
+            .to_str()
+            .and_then(|val| val.strip_prefix(CUSTOM_FORMAT_PREFIX))
+        else {
+            return EnumValueParser::<Format>::new().parse_ref(cmd, arg, value);
+        };
+
+        if StrftimeItems::new(fmt).any(|item| item == Item::Error) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("Invalid strftime format string '{fmt}'\n"),
+            )
+            .with_cmd(cmd));
+        }
+
+        Ok(Format::Custom(fmt.to_owned()))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        let custom = PossibleValue::new("custom:<STRFTIME>")
+            .help("Timestamp is shown using the provided strftime format string e.g. custom:%Y-%m");
+
+        let possible_values = Format::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .chain(iter::once(custom));
+
+        Some(Box::new(possible_values))
+    }
+}
+
+/// Parses `--time-zone` which is either one of the variants of [`Zone`] or a fixed offset from UTC
+/// in the form of `+HH:MM` or `-HH:MM`.
+#[derive(Clone)]
+pub struct ZoneParser;
+
+impl TypedValueParser for ZoneParser {
+    type Value = Zone;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Zone, Error> {
+        let Some(offset) = value.to_str().filter(|val| val.starts_with(['+', '-'])) else {
+            return EnumValueParser::<Zone>::new().parse_ref(cmd, arg, value);
+        };
+
+        parse_offset(offset).map(Zone::Offset).ok_or_else(|| {
+            Error::raw(
+                ErrorKind::InvalidValue,
+                format!("Invalid UTC offset '{offset}'; expected +HH:MM or -HH:MM\n"),
+            )
+            .with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        let offset = PossibleValue::new("<+HH:MM|-HH:MM>").help("A fixed offset from UTC");
+
+        let possible_values = Zone::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .chain(iter::once(offset));
+
+        Some(Box::new(possible_values))
+    }
+}
+
+/// Parses a UTC offset of the form `+HH:MM`, `-HH:MM`, or `+HH`.
+fn parse_offset(offset: &str) -> Option<FixedOffset> {
+    let (sign, hours_minutes) = match offset.split_at(1) {
+        ("+", rest) => (1, rest),
+        ("-", rest) => (-1, rest),
+        _ => return None,
+    };
+
+    let (hours, minutes) = hours_minutes
+        .split_once(':')
+        .unwrap_or((hours_minutes, "0"));
+    let hours = hours.parse::<i32>().ok().filter(|h| (0..24).contains(h))?;
+    let minutes = minutes
+        .parse::<i32>()
+        .ok()
+        .filter(|m| (0..60).contains(m))?;
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[test]
+fn test_relative() {
+    let now = DateTime::<Utc>::from(SystemTime::UNIX_EPOCH) + chrono::Duration::days(1000);
+    let ago = |duration| relative(now, now - duration);
+
+    assert_eq!(ago(chrono::Duration::seconds(30)), "just now");
+    assert_eq!(ago(chrono::Duration::minutes(1)), "1 minute ago");
+    assert_eq!(ago(chrono::Duration::hours(5)), "5 hours ago");
+    assert_eq!(ago(chrono::Duration::days(3)), "3 days ago");
+    assert_eq!(ago(chrono::Duration::days(15)), "2 weeks ago");
+    assert_eq!(ago(chrono::Duration::days(800)), "2 years ago");
+    assert_eq!(ago(chrono::Duration::days(-2)), "in 2 days");
+}
+
+#[test]
+fn test_parse_offset() {
+    assert_eq!(
+        parse_offset("+05:30"),
+        FixedOffset::east_opt(5 * 3600 + 30 * 60)
+    );
+    assert_eq!(parse_offset("-08"), FixedOffset::west_opt(8 * 3600));
+    assert_eq!(parse_offset("+24:00"), None);
+    assert_eq!(parse_offset("05:00"), None);
+}
+
+#[test]
+fn test_age_fraction() {
+    let now = SystemTime::now();
+    let year = std::time::Duration::from_secs(365 * 24 * 60 * 60);
+
+    assert!(age_fraction(now) < f64::EPSILON);
+    assert!(age_fraction(now + year) < f64::EPSILON);
+    assert!((age_fraction(now - 20 * year) - 1.0).abs() < f64::EPSILON);
+
+    let month_old = age_fraction(now - year / 12);
+    assert!(month_old > 0.0 && month_old < age_fraction(now - year));
 }
@@ -1,5 +1,4 @@
-use super::super::units::{BinPrefix, PrefixKind, SiPrefix, UnitPrefix};
-use filesize::PathExt;
+use super::super::units::{BinPrefix, FixedUnit, PrefixKind, SiPrefix, UnitPrefix};
 use std::{
     cell::{Ref, RefCell},
     fmt::{self, Display},
@@ -8,13 +7,16 @@ use std::{
 };
 
 /// Concerned with measuring file size in bytes, whether logical or physical determined by `kind`.
-/// Binary or SI units used for reporting determined by `prefix_kind`.
+/// Binary or SI units used for reporting determined by `prefix_kind` unless a `fixed_unit` is
+/// provided in which case the value is always reported in that unit. The amount of decimal places
+/// used for human-readable output is determined by `precision`.
 pub struct Metric {
     pub value: u64,
     pub human_readable: bool,
-    #[allow(dead_code)]
     kind: MetricKind,
     prefix_kind: PrefixKind,
+    precision: usize,
+    fixed_unit: Option<FixedUnit>,
 
     /// To prevent allocating the same string twice. We allocate the first time
     /// in [`crate::tree::Tree::update_column_properties`] in order to compute the max column width for
@@ -22,9 +24,20 @@ pub struct Metric {
     cached_display: RefCell<String>,
 }
 
+/// How a [Metric] should be presented: whether it is human-readable, which kind of unit prefixes to
+/// use, how many decimal places to show, and optionally a fixed unit to always report in.
+#[derive(Clone, Copy)]
+pub struct Notation {
+    pub human_readable: bool,
+    pub prefix_kind: PrefixKind,
+    pub precision: usize,
+    pub fixed_unit: Option<FixedUnit>,
+}
+
 /// Represents the appropriate method in which to compute bytes. `Logical` represent the total amount
 /// of bytes in a file; `Physical` represents how many bytes are actually used to store the file on
 /// disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MetricKind {
     Logical,
     Physical,
@@ -32,113 +45,113 @@ pub enum MetricKind {
 
 impl Metric {
     /// Initializes a [Metric] that stores the total amount of bytes in a file.
-    pub fn init_logical(
-        metadata: &Metadata,
-        prefix_kind: PrefixKind,
-        human_readable: bool,
-    ) -> Self {
+    pub fn init_logical(metadata: &Metadata, notation: Notation) -> Self {
         let value = metadata.len();
-        let kind = MetricKind::Logical;
 
-        Self {
-            value,
-            human_readable,
-            kind,
-            prefix_kind,
-            cached_display: RefCell::default(),
-        }
+        Self::new(value, MetricKind::Logical, notation)
     }
 
     /// Initializes an empty [Metric] used to represent the total amount of bytes of a file.
-    pub fn init_empty_logical(human_readable: bool, prefix_kind: PrefixKind) -> Self {
-        Self {
-            value: 0,
-            human_readable,
-            kind: MetricKind::Logical,
-            prefix_kind,
-            cached_display: RefCell::default(),
-        }
+    pub const fn init_empty_logical(notation: Notation) -> Self {
+        Self::new(0, MetricKind::Logical, notation)
     }
 
     /// Initializes an empty [Metric] used to represent the total disk space of a file in bytes.
-    pub fn init_empty_physical(human_readable: bool, prefix_kind: PrefixKind) -> Self {
-        Self {
-            value: 0,
-            human_readable,
-            kind: MetricKind::Physical,
-            prefix_kind,
-            cached_display: RefCell::default(),
-        }
+    pub const fn init_empty_physical(notation: Notation) -> Self {
+        Self::new(0, MetricKind::Physical, notation)
     }
 
     /// Initializes a [Metric] that stores the total amount of bytes used to store a file on disk.
-    pub fn init_physical(
-        path: &Path,
-        metadata: &Metadata,
-        prefix_kind: PrefixKind,
-        human_readable: bool,
-    ) -> Self {
-        let value = path.size_on_disk_fast(metadata).unwrap_or(metadata.len());
-        let kind = MetricKind::Physical;
+    pub fn init_physical(path: &Path, metadata: &Metadata, notation: Notation) -> Self {
+        let value = physical_size(path, metadata);
+
+        Self::new(value, MetricKind::Physical, notation)
+    }
+
+    /// Helper constructor shared by all of the initializers.
+    pub const fn new(value: u64, kind: MetricKind, notation: Notation) -> Self {
+        let Notation {
+            human_readable,
+            prefix_kind,
+            precision,
+            fixed_unit,
+        } = notation;
 
         Self {
             value,
             human_readable,
             kind,
             prefix_kind,
-            cached_display: RefCell::default(),
+            precision,
+            fixed_unit,
+            cached_display: RefCell::new(String::new()),
         }
     }
 
+    /// Expresses `value` in terms of the provided `unit` using `precision` decimal places. Values
+    /// reported in bytes are always integral.
+    fn scale_to<U: UnitPrefix + Display>(&self, unit: &U) -> String {
+        let base_value = unit.base_value();
+
+        if base_value == 1 {
+            return format!("{} {unit}", self.value);
+        }
+
+        let precision = self.precision;
+        let size = self.value as f64 / base_value as f64;
+
+        format!("{size:.precision$} {unit}")
+    }
+
+    /// Whether the [Metric] represents logical or physical bytes.
+    pub const fn kind(&self) -> MetricKind {
+        self.kind
+    }
+
     /// Returns an immutable borrow of the `cached_display`.
     pub fn cached_display(&self) -> Ref<'_, String> {
         self.cached_display.borrow()
     }
 }
 
+/// On Unix the physical size is derived from the number of 512-byte blocks allocated to the file,
+/// which `metadata` already holds, so the file doesn't need to be queried again.
+#[cfg(unix)]
+fn physical_size(_path: &Path, metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+
+    metadata.blocks() * 512
+}
+
+/// On Windows the physical size isn't part of `metadata` so the file at `path` has to be queried.
+#[cfg(windows)]
+fn physical_size(path: &Path, metadata: &Metadata) -> u64 {
+    use filesize::PathExt;
+
+    path.size_on_disk_fast(metadata).unwrap_or(metadata.len())
+}
+
+/// Platforms without a notion of allocated size report the logical size.
+#[cfg(not(any(unix, windows)))]
+fn physical_size(_path: &Path, metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
 impl Display for Metric {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         {
             let cached_display = self.cached_display();
 
-            if cached_display.len() > 0 {
+            if !cached_display.is_empty() {
                 return write!(f, "{cached_display}");
             }
         }
 
-        let value = self.value as f64;
-
-        let display = match self.prefix_kind {
-            PrefixKind::Si => {
-                if self.human_readable {
-                    let unit = SiPrefix::from(self.value);
-
-                    if unit == SiPrefix::Base {
-                        format!("{} {unit}", self.value)
-                    } else {
-                        let base_value = unit.base_value();
-                        let size = value / (base_value as f64);
-                        format!("{size:.1} {unit}")
-                    }
-                } else {
-                    format!("{} {}", self.value, SiPrefix::Base)
-                }
-            },
-            PrefixKind::Bin => {
-                if self.human_readable {
-                    let unit = BinPrefix::from(self.value);
-
-                    if unit == BinPrefix::Base {
-                        format!("{} {unit}", self.value)
-                    } else {
-                        let base_value = unit.base_value();
-                        let size = value / (base_value as f64);
-                        format!("{size:.1} {unit}")
-                    }
-                } else {
-                    format!("{} {}", self.value, BinPrefix::Base)
-                }
-            },
+        let display = match (self.fixed_unit, self.prefix_kind) {
+            (Some(unit), _) => self.scale_to(&unit),
+            (None, _) if !self.human_readable => format!("{} {}", self.value, BinPrefix::Base),
+            (None, PrefixKind::Si) => self.scale_to(&SiPrefix::from(self.value)),
+            (None, PrefixKind::Bin) => self.scale_to(&BinPrefix::from(self.value)),
         };
 
         write!(f, "{display}")?;
@@ -156,6 +169,8 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: false,
         prefix_kind: PrefixKind::Bin,
+        precision: 1,
+        fixed_unit: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "100 B");
@@ -165,6 +180,8 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Si,
+        precision: 1,
+        fixed_unit: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 KB");
@@ -174,6 +191,8 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        precision: 1,
+        fixed_unit: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1000 B");
@@ -183,6 +202,8 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        precision: 1,
+        fixed_unit: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 KiB");
@@ -192,6 +213,8 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        precision: 1,
+        fixed_unit: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 MiB");
@@ -201,7 +224,61 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: false,
         prefix_kind: PrefixKind::Bin,
+        precision: 1,
+        fixed_unit: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "123454 B");
+
+    let metric = Metric {
+        value: 3 * 2_u64.pow(50),
+        kind: MetricKind::Logical,
+        human_readable: true,
+        prefix_kind: PrefixKind::Bin,
+        precision: 1,
+        fixed_unit: None,
+        cached_display: RefCell::<String>::default(),
+    };
+    assert_eq!(format!("{metric}"), "3.0 PiB");
+}
+
+#[test]
+fn test_metric_notation() {
+    let notation = Notation {
+        human_readable: true,
+        prefix_kind: PrefixKind::Bin,
+        precision: 3,
+        fixed_unit: None,
+    };
+
+    let metric = Metric::new(1536, MetricKind::Logical, notation);
+    assert_eq!(format!("{metric}"), "1.500 KiB");
+
+    let notation = Notation {
+        precision: 0,
+        ..notation
+    };
+
+    let metric = Metric::new(1536, MetricKind::Logical, notation);
+    assert_eq!(format!("{metric}"), "2 KiB");
+
+    let notation = Notation {
+        precision: 2,
+        fixed_unit: Some(FixedUnit::Mebi),
+        ..notation
+    };
+
+    let metric = Metric::new(1536, MetricKind::Logical, notation);
+    assert_eq!(format!("{metric}"), "0.00 MiB");
+
+    let metric = Metric::new(3 * 2_u64.pow(30), MetricKind::Logical, notation);
+    assert_eq!(format!("{metric}"), "3072.00 MiB");
+
+    let notation = Notation {
+        fixed_unit: Some(FixedUnit::Base),
+        ..notation
+    };
+
+    let metric = Metric::new(3 * 2_u64.pow(30), MetricKind::Logical, notation);
+    assert_eq!(format!("{metric}"), "3221225472 B");
 }
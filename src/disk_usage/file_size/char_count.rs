@@ -0,0 +1,40 @@
+use super::text::Tally;
+use std::{
+    convert::From,
+    fmt::{self, Display},
+};
+
+/// Concerned with measuring file size using character count as a metric.
+#[derive(Default)]
+pub struct Metric {
+    pub value: u64,
+}
+
+impl From<u64> for Metric {
+    fn from(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl From<&Tally> for Metric {
+    /// The total number of characters in a file that was streamed as text.
+    fn from(tally: &Tally) -> Self {
+        Self::from(tally.chars)
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <u64 as Display>::fmt(&self.value, f)
+    }
+}
+
+#[test]
+fn test_char_count() {
+    let tally = super::text::tally(std::path::Path::new("tests/data/nemesis.txt"))
+        .expect("Expected 'tests/data/nemesis.txt' to exist");
+
+    let metric = Metric::from(&tally);
+
+    assert_eq!(metric.value, 159);
+}
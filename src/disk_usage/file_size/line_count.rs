@@ -1,8 +1,7 @@
+use super::text::Tally;
 use std::{
-    convert::{AsRef, From},
+    convert::From,
     fmt::{self, Display},
-    fs,
-    path::Path,
 };
 
 /// Concerned with measuring file size using line count as a metric.
@@ -11,25 +10,19 @@ pub struct Metric {
     pub value: u64,
 }
 
-impl Metric {
-    /// Reads in contents of a file given by `path` and attempts to compute the total number of
-    /// lines in that file. If a file is not UTF-8 encoded as in the case of a binary jpeg file
-    /// then `None` will be returned.
-    pub fn init(path: impl AsRef<Path>) -> Option<Self> {
-        let data = fs::read_to_string(path.as_ref()).ok()?;
-
-        let lines = data.lines().count();
-
-        u64::try_from(lines).map(|value| Self { value }).ok()
-    }
-}
-
 impl From<u64> for Metric {
     fn from(value: u64) -> Self {
         Self { value }
     }
 }
 
+impl From<&Tally> for Metric {
+    /// The total number of lines in a file that was streamed as text.
+    fn from(tally: &Tally) -> Self {
+        Self::from(tally.lines)
+    }
+}
+
 impl Display for Metric {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         <u64 as Display>::fmt(&self.value, f)
@@ -38,8 +31,10 @@ impl Display for Metric {
 
 #[test]
 fn test_line_count() {
-    let metric =
-        Metric::init("tests/data/nemesis.txt").expect("Expected 'tests/data/nemesis.txt' to exist");
+    let tally = super::text::tally(std::path::Path::new("tests/data/nemesis.txt"))
+        .expect("Expected 'tests/data/nemesis.txt' to exist");
+
+    let metric = Metric::from(&tally);
 
     assert_eq!(metric.value, 4);
 }
@@ -3,9 +3,14 @@ use clap::ValueEnum;
 use std::{
     convert::From,
     fmt::{self, Display},
+    fs::Metadata,
     ops::AddAssign,
+    path::Path,
 };
 
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use crate::fs::statx::Statx;
+
 /// Concerned with measuring file size in blocks.
 #[cfg(unix)]
 pub mod block;
@@ -19,6 +24,21 @@ pub mod line_count;
 /// Concerned with measuring file size by word count.
 pub mod word_count;
 
+/// Concerned with measuring file size by character count.
+pub mod char_count;
+
+/// Concerned with measuring file size by the count of lines that aren't blank.
+pub mod nonblank_line_count;
+
+/// Concerned with measuring file size by source lines of code broken down by language.
+pub mod sloc;
+
+/// Streaming analysis of text files with bounded memory usage.
+pub mod text;
+
+/// What gets reported in place of a text-based metric for files that are binary.
+pub const BINARY: &str = "binary";
+
 #[cfg(unix)]
 pub const BLOCK_SIZE_BYTES: u16 = 512;
 
@@ -26,13 +46,19 @@ pub const BLOCK_SIZE_BYTES: u16 = 512;
 pub enum FileSize {
     Word(word_count::Metric),
     Line(line_count::Metric),
+    NonBlankLine(nonblank_line_count::Metric),
+    Char(char_count::Metric),
+    Sloc(sloc::Metric),
     Byte(byte::Metric),
     #[cfg(unix)]
     Block(block::Metric),
+
+    /// A text-based metric that could not be computed because the file is binary.
+    Binary(DiskUsage),
 }
 
 /// Determines between logical or physical size for display
-#[derive(Copy, Clone, Debug, ValueEnum, Default)]
+#[derive(Copy, Clone, Debug, ValueEnum, Default, PartialEq, Eq, Hash)]
 pub enum DiskUsage {
     /// How many bytes does a file contain
     Logical,
@@ -47,12 +73,185 @@ pub enum DiskUsage {
     /// How many total words a file contains
     Word,
 
+    /// How many total characters a file contains
+    Char,
+
+    /// How many lines that aren't blank a file contains
+    NonBlank,
+
+    /// How many lines of source code a file contains, excluding comments and blank lines
+    Sloc,
+
     /// How many blocks are allocated to store the file
     #[cfg(unix)]
     Block,
 }
 
+impl DiskUsage {
+    /// Answers whether the metric is measured in bytes.
+    pub const fn is_byte_metric(self) -> bool {
+        matches!(self, Self::Logical | Self::Physical)
+    }
+}
+
+/// The text-based metrics of a single file which are all computed together in one pass over the
+/// file the first time that any one of them is needed.
+pub struct Text<'a> {
+    path: &'a Path,
+    language: Option<sloc::Language>,
+    read: Option<Result<(text::Tally, Option<sloc::Metric>), text::Error>>,
+}
+
+impl<'a> Text<'a> {
+    /// The language of the file at `path` is only determined if source lines of code are among
+    /// the metrics that are to be computed.
+    pub fn new(path: &'a Path, ctx: &Context) -> Self {
+        let sloc = ctx.disk_usage() == DiskUsage::Sloc
+            || ctx.secondary_disk_usages().contains(&DiskUsage::Sloc);
+
+        Self {
+            path,
+            language: sloc.then(|| sloc::Language::from_path(path)).flatten(),
+            read: None,
+        }
+    }
+
+    /// Computes the text-based `disk_usage` metric, reading the file if it hasn't been already.
+    /// Metrics are `None` for files that can't be read, source lines of code are `None` for files
+    /// of an unrecognized language, and metrics are [`FileSize::Binary`] for files that aren't
+    /// UTF-8 encoded text. Returns `None` for any metric that isn't text-based.
+    pub fn get(&mut self, disk_usage: DiskUsage) -> Option<FileSize> {
+        if disk_usage == DiskUsage::Sloc && self.language.is_none() {
+            return None;
+        }
+
+        let (path, language) = (self.path, self.language);
+
+        let read = self.read.get_or_insert_with(|| {
+            language.map_or_else(
+                || text::tally(path).map(|tally| (tally, None)),
+                |language| {
+                    sloc::Metric::init(path, language).map(|(sloc, tally)| (tally, Some(sloc)))
+                },
+            )
+        });
+
+        let (tally, sloc) = match read {
+            Ok((tally, sloc)) => (*tally, sloc),
+            Err(text::Error::Binary) => return Some(FileSize::Binary(disk_usage)),
+            Err(text::Error::Io(_)) => return None,
+        };
+
+        match disk_usage {
+            DiskUsage::Line => Some(FileSize::Line(line_count::Metric::from(&tally))),
+            DiskUsage::Word => Some(FileSize::Word(word_count::Metric::from(&tally))),
+            DiskUsage::Char => Some(FileSize::Char(char_count::Metric::from(&tally))),
+            DiskUsage::NonBlank => Some(FileSize::NonBlankLine(nonblank_line_count::Metric::from(
+                &tally,
+            ))),
+            DiskUsage::Sloc => sloc.take().map(FileSize::Sloc),
+            _ => None,
+        }
+    }
+}
+
 impl FileSize {
+    /// Initializes an empty [`FileSize`] for the provided `disk_usage` metric which is used to
+    /// accumulate the sizes of a directory's contents.
+    pub fn init_empty(disk_usage: DiskUsage, ctx: &Context) -> Self {
+        let notation = byte::Notation::from(ctx);
+
+        match disk_usage {
+            DiskUsage::Logical => Self::Byte(byte::Metric::init_empty_logical(notation)),
+            DiskUsage::Physical => Self::Byte(byte::Metric::init_empty_physical(notation)),
+            DiskUsage::Line => Self::Line(line_count::Metric::default()),
+            DiskUsage::Word => Self::Word(word_count::Metric::default()),
+            DiskUsage::Char => Self::Char(char_count::Metric::default()),
+            DiskUsage::NonBlank => Self::NonBlankLine(nonblank_line_count::Metric::default()),
+            DiskUsage::Sloc => Self::Sloc(sloc::Metric::default()),
+
+            #[cfg(unix)]
+            DiskUsage::Block => Self::Block(block::Metric::default()),
+        }
+    }
+
+    /// Attempts to compute the [`FileSize`] of the file at `path` using the provided `disk_usage`
+    /// metric. Text-based metrics come from `text` which reads the file at most once no matter
+    /// how many of them are requested; see [`Text::get`].
+    pub fn try_init(
+        disk_usage: DiskUsage,
+        path: &Path,
+        metadata: &Metadata,
+        text: &mut Text,
+        ctx: &Context,
+    ) -> Option<Self> {
+        match disk_usage {
+            DiskUsage::Logical => {
+                let notation = byte::Notation::from(ctx);
+                let metric = byte::Metric::init_logical(metadata, notation);
+                Some(Self::Byte(metric))
+            },
+            DiskUsage::Physical => {
+                let notation = byte::Notation::from(ctx);
+                let metric = byte::Metric::init_physical(path, metadata, notation);
+                Some(Self::Byte(metric))
+            },
+
+            #[cfg(unix)]
+            DiskUsage::Block => Some(Self::Block(block::Metric::init(metadata))),
+
+            _ => text.get(disk_usage),
+        }
+    }
+
+    /// Like [`FileSize::try_init`] but with the size and allocated blocks of the file coming from
+    /// `statx(2)` rather than from its [`Metadata`].
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    pub fn try_init_statx(
+        disk_usage: DiskUsage,
+        statx: &Statx,
+        text: &mut Text,
+        ctx: &Context,
+    ) -> Option<Self> {
+        match disk_usage {
+            DiskUsage::Logical => {
+                let notation = byte::Notation::from(ctx);
+                let metric = byte::Metric::new(statx.len(), byte::MetricKind::Logical, notation);
+                Some(Self::Byte(metric))
+            },
+            DiskUsage::Physical => {
+                let notation = byte::Notation::from(ctx);
+                let value = statx.blocks() * 512;
+                let metric = byte::Metric::new(value, byte::MetricKind::Physical, notation);
+                Some(Self::Byte(metric))
+            },
+            DiskUsage::Block => Some(Self::Block(block::Metric {
+                value: statx.blocks(),
+            })),
+            _ => text.get(disk_usage),
+        }
+    }
+
+    /// The disk usage metric that this [`FileSize`] represents.
+    pub const fn disk_usage(&self) -> DiskUsage {
+        match self {
+            Self::Byte(metric) => match metric.kind() {
+                byte::MetricKind::Logical => DiskUsage::Logical,
+                byte::MetricKind::Physical => DiskUsage::Physical,
+            },
+            Self::Line(_) => DiskUsage::Line,
+            Self::Word(_) => DiskUsage::Word,
+            Self::Char(_) => DiskUsage::Char,
+            Self::NonBlankLine(_) => DiskUsage::NonBlank,
+            Self::Sloc(_) => DiskUsage::Sloc,
+
+            #[cfg(unix)]
+            Self::Block(_) => DiskUsage::Block,
+
+            Self::Binary(disk_usage) => *disk_usage,
+        }
+    }
+
     /// Extracts the inner value of [`FileSize`] which represents the file size for various metrics.
     #[inline]
     pub const fn value(&self) -> u64 {
@@ -60,9 +259,14 @@ impl FileSize {
             Self::Byte(metric) => metric.value,
             Self::Line(metric) => metric.value,
             Self::Word(metric) => metric.value,
+            Self::Char(metric) => metric.value,
+            Self::NonBlankLine(metric) => metric.value,
+            Self::Sloc(metric) => metric.value,
 
             #[cfg(unix)]
             Self::Block(metric) => metric.value,
+
+            Self::Binary(_) => 0,
         }
     }
 }
@@ -73,25 +277,32 @@ impl AddAssign<&Self> for FileSize {
             Self::Byte(metric) => metric.value += rhs.value(),
             Self::Line(metric) => metric.value += rhs.value(),
             Self::Word(metric) => metric.value += rhs.value(),
+            Self::Char(metric) => metric.value += rhs.value(),
+            Self::NonBlankLine(metric) => metric.value += rhs.value(),
+            Self::Sloc(metric) => metric.value += rhs.value(),
 
             #[cfg(unix)]
             Self::Block(metric) => metric.value += rhs.value(),
+
+            Self::Binary(_) => (),
         }
     }
 }
 
 impl From<&Context> for FileSize {
+    /// Initializes an empty [`FileSize`] for the primary disk usage metric.
     fn from(ctx: &Context) -> Self {
-        use DiskUsage::{Line, Logical, Physical, Word};
-
-        match ctx.disk_usage {
-            Logical => Self::Byte(byte::Metric::init_empty_logical(ctx.human, ctx.unit)),
-            Physical => Self::Byte(byte::Metric::init_empty_physical(ctx.human, ctx.unit)),
-            Line => Self::Line(line_count::Metric::default()),
-            Word => Self::Word(word_count::Metric::default()),
+        Self::init_empty(ctx.disk_usage(), ctx)
+    }
+}
 
-            #[cfg(unix)]
-            DiskUsage::Block => Self::Block(block::Metric::default()),
+impl From<&Context> for byte::Notation {
+    fn from(ctx: &Context) -> Self {
+        Self {
+            human_readable: ctx.human_readable(),
+            prefix_kind: ctx.unit,
+            precision: ctx.precision(),
+            fixed_unit: ctx.unit_fixed,
         }
     }
 }
@@ -102,9 +313,14 @@ impl Display for FileSize {
             Self::Word(metric) => write!(f, "{metric}"),
             Self::Line(metric) => write!(f, "{metric}"),
             Self::Byte(metric) => write!(f, "{metric}"),
+            Self::Char(metric) => write!(f, "{metric}"),
+            Self::NonBlankLine(metric) => write!(f, "{metric}"),
+            Self::Sloc(metric) => write!(f, "{metric}"),
 
             #[cfg(unix)]
             Self::Block(metric) => write!(f, "{metric}"),
+
+            Self::Binary(_) => write!(f, "{BINARY}"),
         }
     }
 }
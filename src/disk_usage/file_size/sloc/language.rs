@@ -0,0 +1,252 @@
+use crate::hash;
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    path::Path,
+};
+
+/// Programming and markup languages whose source lines of code can be counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+    C,
+    Clojure,
+    Cpp,
+    CSharp,
+    Css,
+    Dart,
+    Elixir,
+    Erlang,
+    Go,
+    Haskell,
+    Html,
+    Java,
+    JavaScript,
+    Kotlin,
+    Lisp,
+    Lua,
+    Markdown,
+    Nim,
+    OCaml,
+    Perl,
+    Php,
+    Python,
+    R,
+    Ruby,
+    Rust,
+    Scala,
+    Scss,
+    Shell,
+    Sql,
+    Swift,
+    Toml,
+    TypeScript,
+    Xml,
+    Yaml,
+    Zig,
+}
+
+/// Comment delimiters of a [Language]. No delimiter may be a prefix of another.
+pub struct Syntax {
+    /// Delimiters that begin a comment that runs until the end of the line.
+    pub line: &'static [&'static str],
+
+    /// Pairs of delimiters that begin and end a comment that may span multiple lines.
+    pub block: &'static [(&'static str, &'static str)],
+}
+
+const C_LIKE: Syntax = Syntax {
+    line: &["//"],
+    block: &[("/*", "*/")],
+};
+
+const HASH: Syntax = Syntax {
+    line: &["#"],
+    block: &[],
+};
+
+const DOUBLE_DASH: Syntax = Syntax {
+    line: &["--"],
+    block: &[],
+};
+
+const SEMICOLON: Syntax = Syntax {
+    line: &[";"],
+    block: &[],
+};
+
+const MARKUP: Syntax = Syntax {
+    line: &[],
+    block: &[("<!--", "-->")],
+};
+
+impl Language {
+    /// Attempts to classify the file at `path` by its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+
+        EXT_LANGUAGE_MAP
+            .get(ext)
+            .or_else(|| EXT_LANGUAGE_MAP.get(ext.to_ascii_lowercase().as_str()))
+            .copied()
+    }
+
+    /// Human readable name of the language.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::C => "C",
+            Self::Clojure => "Clojure",
+            Self::Cpp => "C++",
+            Self::CSharp => "C#",
+            Self::Css => "CSS",
+            Self::Dart => "Dart",
+            Self::Elixir => "Elixir",
+            Self::Erlang => "Erlang",
+            Self::Go => "Go",
+            Self::Haskell => "Haskell",
+            Self::Html => "HTML",
+            Self::Java => "Java",
+            Self::JavaScript => "JavaScript",
+            Self::Kotlin => "Kotlin",
+            Self::Lisp => "Lisp",
+            Self::Lua => "Lua",
+            Self::Markdown => "Markdown",
+            Self::Nim => "Nim",
+            Self::OCaml => "OCaml",
+            Self::Perl => "Perl",
+            Self::Php => "PHP",
+            Self::Python => "Python",
+            Self::R => "R",
+            Self::Ruby => "Ruby",
+            Self::Rust => "Rust",
+            Self::Scala => "Scala",
+            Self::Scss => "SCSS",
+            Self::Shell => "Shell",
+            Self::Sql => "SQL",
+            Self::Swift => "Swift",
+            Self::Toml => "TOML",
+            Self::TypeScript => "TypeScript",
+            Self::Xml => "XML",
+            Self::Yaml => "YAML",
+            Self::Zig => "Zig",
+        }
+    }
+
+    /// The comment [Syntax] of the language.
+    pub const fn syntax(self) -> Syntax {
+        match self {
+            Self::C
+            | Self::Cpp
+            | Self::CSharp
+            | Self::Dart
+            | Self::Go
+            | Self::Java
+            | Self::JavaScript
+            | Self::Kotlin
+            | Self::Rust
+            | Self::Scala
+            | Self::Scss
+            | Self::Swift
+            | Self::TypeScript => C_LIKE,
+
+            Self::Elixir
+            | Self::Nim
+            | Self::Perl
+            | Self::Python
+            | Self::R
+            | Self::Ruby
+            | Self::Shell
+            | Self::Toml
+            | Self::Yaml => HASH,
+
+            Self::Lua => DOUBLE_DASH,
+
+            Self::Clojure | Self::Lisp => SEMICOLON,
+
+            Self::Html | Self::Markdown | Self::Xml => MARKUP,
+
+            Self::Css => Syntax {
+                line: &[],
+                block: &[("/*", "*/")],
+            },
+
+            Self::Erlang => Syntax {
+                line: &["%"],
+                block: &[],
+            },
+
+            Self::Haskell => Syntax {
+                line: &["--"],
+                block: &[("{-", "-}")],
+            },
+
+            Self::OCaml => Syntax {
+                line: &[],
+                block: &[("(*", "*)")],
+            },
+
+            Self::Php => Syntax {
+                line: &["//", "#"],
+                block: &[("/*", "*/")],
+            },
+
+            Self::Sql => Syntax {
+                line: &["--"],
+                block: &[("/*", "*/")],
+            },
+
+            Self::Zig => Syntax {
+                line: &["//"],
+                block: &[],
+            },
+        }
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Lazily evaluated static hash-map of file extensions and the language they're associated with.
+/// Extensions are largely the same as those found in the icon tables.
+static EXT_LANGUAGE_MAP: Lazy<HashMap<&str, Language>> = Lazy::new(|| {
+    hash!(
+        "c" | "h"                                          => Language::C,
+        "clj" | "cljc" | "cljd" | "cljs" | "edn"           => Language::Clojure,
+        "c++" | "cc" | "cp" | "cpp" | "cxx" | "hh" | "hpp" => Language::Cpp,
+        "cs"                                               => Language::CSharp,
+        "css"                                              => Language::Css,
+        "dart"                                             => Language::Dart,
+        "ex" | "exs"                                       => Language::Elixir,
+        "erl" | "hrl"                                      => Language::Erlang,
+        "go"                                               => Language::Go,
+        "hs" | "lhs"                                       => Language::Haskell,
+        "htm" | "html"                                     => Language::Html,
+        "java"                                             => Language::Java,
+        "cjs" | "js" | "jsx" | "mjs"                       => Language::JavaScript,
+        "kt" | "kts"                                       => Language::Kotlin,
+        "el" | "lisp" | "lsp"                              => Language::Lisp,
+        "lua"                                              => Language::Lua,
+        "markdown" | "md"                                  => Language::Markdown,
+        "nim"                                              => Language::Nim,
+        "ml" | "mli"                                       => Language::OCaml,
+        "pl" | "pm"                                        => Language::Perl,
+        "php"                                              => Language::Php,
+        "py" | "pyi"                                       => Language::Python,
+        "r"                                                => Language::R,
+        "rake" | "rb"                                      => Language::Ruby,
+        "rs"                                               => Language::Rust,
+        "sc" | "scala"                                     => Language::Scala,
+        "sass" | "scss"                                    => Language::Scss,
+        "bash" | "fish" | "ksh" | "sh" | "zsh"             => Language::Shell,
+        "sql"                                              => Language::Sql,
+        "swift"                                            => Language::Swift,
+        "toml"                                             => Language::Toml,
+        "cts" | "mts" | "ts" | "tsx"                       => Language::TypeScript,
+        "svg" | "xml" | "xsd" | "xsl"                      => Language::Xml,
+        "yaml" | "yml"                                     => Language::Yaml,
+        "zig"                                              => Language::Zig
+    )
+});
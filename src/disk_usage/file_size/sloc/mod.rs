@@ -0,0 +1,243 @@
+use super::text::{self, Sink};
+use std::{
+    convert::From,
+    fmt::{self, Display},
+    path::Path,
+};
+
+/// Classification of files by extension and the comment syntax of each language.
+pub mod language;
+
+/// Per-language totals of source lines of code.
+pub mod summary;
+
+pub use language::Language;
+pub use summary::Summary;
+
+/// Concerned with measuring file size using source lines of code as a metric. The `value` is the
+/// amount of lines containing code; lines that only contain comments or whitespace are tallied
+/// separately.
+#[derive(Default)]
+pub struct Metric {
+    pub value: u64,
+    pub comments: u64,
+    pub blanks: u64,
+    pub language: Option<Language>,
+}
+
+impl Metric {
+    /// Streams the contents of a file given by `path` and classifies each line as code, comment,
+    /// or blank according to the comment syntax of `language`. If a file is not UTF-8 encoded
+    /// then [`text::Error::Binary`] will be returned. The [`text::Tally`] of the file is computed
+    /// during the same pass.
+    ///
+    /// Comment delimiters that appear inside of string literals are not accounted for.
+    pub fn init(
+        path: impl AsRef<Path>,
+        language: Language,
+    ) -> Result<(Self, text::Tally), text::Error> {
+        let mut classifier = Classifier::new(language);
+        let tally = text::tally_with(path.as_ref(), &mut classifier)?;
+        Ok((classifier.finish(), tally))
+    }
+}
+
+impl From<u64> for Metric {
+    fn from(value: u64) -> Self {
+        Self {
+            value,
+            ..Self::default()
+        }
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <u64 as Display>::fmt(&self.value, f)
+    }
+}
+
+/// Where in the source text the [Classifier] currently is.
+enum State {
+    Code,
+    LineComment,
+    BlockComment(&'static str),
+}
+
+/// Classifies lines of streamed source text as code, comment, or blank.
+struct Classifier {
+    metric: Metric,
+    syntax: language::Syntax,
+    state: State,
+
+    /// Characters that might be the beginning of a comment delimiter. This is never longer than
+    /// the longest delimiter.
+    pending: String,
+
+    line_is_empty: bool,
+    line_has_code: bool,
+    line_has_comment: bool,
+}
+
+impl Classifier {
+    fn new(language: Language) -> Self {
+        Self {
+            metric: Metric {
+                language: Some(language),
+                ..Metric::default()
+            },
+            syntax: language.syntax(),
+            state: State::Code,
+            pending: String::new(),
+            line_is_empty: true,
+            line_has_code: false,
+            line_has_comment: false,
+        }
+    }
+
+    /// Handles a character outside of a comment.
+    fn code(&mut self, ch: char) {
+        self.pending.push(ch);
+
+        let syntax = &self.syntax;
+        let is_delimiter_prefix = |pending: &str| {
+            syntax.line.iter().any(|start| start.starts_with(pending))
+                || syntax
+                    .block
+                    .iter()
+                    .any(|(start, _)| start.starts_with(pending))
+        };
+
+        while !self.pending.is_empty() && !is_delimiter_prefix(&self.pending) {
+            let ch = self.pending.remove(0);
+            self.line_has_code |= !ch.is_whitespace();
+        }
+
+        if self.syntax.line.contains(&self.pending.as_str()) {
+            self.pending.clear();
+            self.line_has_comment = true;
+            self.state = State::LineComment;
+        } else if let Some((_, end)) = self
+            .syntax
+            .block
+            .iter()
+            .find(|(start, _)| *start == self.pending)
+        {
+            self.pending.clear();
+            self.line_has_comment = true;
+            self.state = State::BlockComment(end);
+        }
+    }
+
+    /// Handles a character inside of a block comment that is terminated by `end`.
+    fn block_comment(&mut self, ch: char, end: &'static str) {
+        self.line_has_comment |= !ch.is_whitespace();
+        self.pending.push(ch);
+
+        while !self.pending.is_empty() && !end.starts_with(self.pending.as_str()) {
+            self.pending.remove(0);
+        }
+
+        if self.pending == end {
+            self.pending.clear();
+            self.state = State::Code;
+        }
+    }
+
+    /// Classifies the line that was just terminated.
+    fn end_line(&mut self) {
+        if matches!(self.state, State::Code) {
+            self.line_has_code |= self.pending.chars().any(|ch| !ch.is_whitespace());
+        }
+
+        if self.line_has_code {
+            self.metric.value += 1;
+        } else if self.line_has_comment {
+            self.metric.comments += 1;
+        } else {
+            self.metric.blanks += 1;
+        }
+
+        if matches!(self.state, State::LineComment) {
+            self.state = State::Code;
+        }
+
+        self.pending.clear();
+        self.line_is_empty = true;
+        self.line_has_code = false;
+        self.line_has_comment = false;
+    }
+
+    /// Accounts for a trailing line without a newline and returns the final [Metric].
+    fn finish(mut self) -> Metric {
+        if !self.line_is_empty {
+            self.end_line();
+        }
+        self.metric
+    }
+}
+
+impl Sink for Classifier {
+    fn feed(&mut self, text: &str) {
+        for ch in text.chars() {
+            if ch == '\n' {
+                self.end_line();
+                continue;
+            }
+
+            self.line_is_empty = false;
+
+            match self.state {
+                State::Code => self.code(ch),
+                State::LineComment => (),
+                State::BlockComment(end) => self.block_comment(ch, end),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_sloc() -> Result<(), text::Error> {
+    let source = "\
+//! Module docs
+
+/* A block comment
+   spanning three lines
+
+*/
+fn main() { /* inline */
+    let x = 1; // trailing comment
+    /* a */ let y = x / 2; /* b
+    */
+}";
+
+    // Small buffers split comment delimiters across reads.
+    for buffer_size in [4, 5, 1024] {
+        let mut buffer = vec![0; buffer_size];
+        let mut classifier = Classifier::new(Language::Rust);
+        text::stream_str(source, &mut buffer, &mut classifier)?;
+        let metric = classifier.finish();
+
+        assert_eq!(metric.value, 4);
+        assert_eq!(metric.comments, 5);
+        assert_eq!(metric.blanks, 2);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_sloc_hash_comments() -> Result<(), text::Error> {
+    let source = "#!/usr/bin/env python3\n\n# comment\nprint('#')  # trailing\n";
+
+    let mut buffer = [0; 8];
+    let mut classifier = Classifier::new(Language::Python);
+    text::stream_str(source, &mut buffer, &mut classifier)?;
+    let metric = classifier.finish();
+
+    assert_eq!(metric.value, 1);
+    assert_eq!(metric.comments, 2);
+    assert_eq!(metric.blanks, 1);
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use super::{Language, Metric};
+use crate::utils;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+/// Totals of source lines of code broken down by [Language].
+#[derive(Clone, Default)]
+pub struct Summary {
+    entries: HashMap<Language, Entry>,
+}
+
+/// Totals for a single [Language].
+#[derive(Clone, Copy, Default)]
+struct Entry {
+    files: u64,
+    code: u64,
+    comments: u64,
+    blanks: u64,
+}
+
+impl Summary {
+    /// Accounts for a single file's [Metric]. Metrics without a language, as is the case for
+    /// directories, are ignored.
+    pub fn add(&mut self, metric: &Metric) {
+        let Some(language) = metric.language else {
+            return;
+        };
+
+        let entry = self.entries.entry(language).or_default();
+        entry.files += 1;
+        entry.code += metric.value;
+        entry.comments += metric.comments;
+        entry.blanks += metric.blanks;
+    }
+
+    /// Whether or not any files have been accounted for.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Display for Summary {
+    /// Renders a table with a row per language sorted by lines of code in descending order
+    /// followed by the totals.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = self
+            .entries
+            .iter()
+            .map(|(language, entry)| (language.name(), *entry))
+            .collect::<Vec<_>>();
+
+        rows.sort_by(|(name_a, a), (name_b, b)| b.code.cmp(&a.code).then(name_a.cmp(name_b)));
+
+        let total = rows.iter().fold(Entry::default(), |acc, (_, entry)| Entry {
+            files: acc.files + entry.files,
+            code: acc.code + entry.code,
+            comments: acc.comments + entry.comments,
+            blanks: acc.blanks + entry.blanks,
+        });
+
+        rows.push(("Total", total));
+
+        let headers = ["Language", "Files", "Code", "Comments", "Blanks"];
+
+        let name_width = rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .fold(headers[0].len(), usize::max);
+
+        let num_width = |header: &str, num: u64| header.len().max(utils::num_integral(num));
+        let files_width = num_width(headers[1], total.files);
+        let code_width = num_width(headers[2], total.code);
+        let comments_width = num_width(headers[3], total.comments);
+        let blanks_width = num_width(headers[4], total.blanks);
+
+        write!(
+            f,
+            "{:<name_width$}  {:>files_width$}  {:>code_width$}  {:>comments_width$}  {:>blanks_width$}",
+            headers[0], headers[1], headers[2], headers[3], headers[4]
+        )?;
+
+        for (name, entry) in rows {
+            write!(
+                f,
+                "\n{name:<name_width$}  {:>files_width$}  {:>code_width$}  {:>comments_width$}  {:>blanks_width$}",
+                entry.files, entry.code, entry.comments, entry.blanks
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    fs::File,
+    io::{self, ErrorKind, Read},
+    path::Path,
+    str,
+};
+
+/// Size of the buffer used to stream files from disk. Memory usage is bounded by this regardless
+/// of how large the file being read is.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// The various counts that are gathered from a single pass over a text file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub lines: u64,
+    pub nonblank_lines: u64,
+    pub words: u64,
+    pub chars: u64,
+}
+
+/// Reasons why a file could not be streamed as text.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("File is not UTF-8 encoded text")]
+    Binary,
+
+    #[error("{0}")]
+    Io(#[from] io::Error),
+}
+
+/// Consumer of text that is streamed in chunks. Each chunk directly follows the previous one and
+/// never splits a UTF-8 encoded character.
+pub trait Sink {
+    fn feed(&mut self, text: &str);
+}
+
+/// Keeps track of where we are relative to lines and words as chunks of text are fed in.
+#[derive(Default)]
+struct Cursor {
+    tally: Tally,
+    in_word: bool,
+    line_is_empty: bool,
+    line_is_blank: bool,
+}
+
+impl Cursor {
+    fn new() -> Self {
+        Self {
+            line_is_empty: true,
+            line_is_blank: true,
+            ..Self::default()
+        }
+    }
+
+    /// Accounts for the line that was just terminated.
+    fn end_line(&mut self) {
+        self.tally.lines += 1;
+
+        if !self.line_is_blank {
+            self.tally.nonblank_lines += 1;
+        }
+
+        self.line_is_empty = true;
+        self.line_is_blank = true;
+    }
+
+    /// Accounts for a trailing line without a newline and returns the final [Tally].
+    fn finish(mut self) -> Tally {
+        if !self.line_is_empty {
+            self.end_line();
+        }
+        self.tally
+    }
+}
+
+impl Sink for Cursor {
+    /// Updates the tally with the contents of `text`.
+    fn feed(&mut self, text: &str) {
+        for ch in text.chars() {
+            self.tally.chars += 1;
+
+            if ch == '\n' {
+                self.end_line();
+                self.in_word = false;
+                continue;
+            }
+
+            self.line_is_empty = false;
+
+            if ch.is_whitespace() {
+                self.in_word = false;
+            } else {
+                self.line_is_blank = false;
+
+                if !self.in_word {
+                    self.tally.words += 1;
+                    self.in_word = true;
+                }
+            }
+        }
+    }
+}
+
+/// Feeds the same text into a [Cursor] as well as another [Sink] so that both are served by a
+/// single pass over the file.
+struct Tee<'a, S> {
+    cursor: Cursor,
+    sink: &'a mut S,
+}
+
+impl<S: Sink> Sink for Tee<'_, S> {
+    fn feed(&mut self, text: &str) {
+        self.cursor.feed(text);
+        self.sink.feed(text);
+    }
+}
+
+/// Streams the file at `path` computing its [Tally]. Files that contain a NUL byte or that aren't
+/// valid UTF-8 are considered binary and result in [`Error::Binary`].
+pub fn tally(path: &Path) -> Result<Tally, Error> {
+    let mut cursor = Cursor::new();
+    stream(path, &mut cursor)?;
+    Ok(cursor.finish())
+}
+
+/// Like [tally] but the file is also streamed into `sink` during the same pass.
+pub fn tally_with<S: Sink>(path: &Path, sink: &mut S) -> Result<Tally, Error> {
+    let mut tee = Tee {
+        cursor: Cursor::new(),
+        sink,
+    };
+    stream(path, &mut tee)?;
+    Ok(tee.cursor.finish())
+}
+
+/// Streams the file at `path` into `sink`. Files that contain a NUL byte or that aren't valid
+/// UTF-8 result in [`Error::Binary`].
+pub fn stream<S: Sink>(path: &Path, sink: &mut S) -> Result<(), Error> {
+    let file = File::open(path)?;
+    let mut buffer = vec![0; BUFFER_SIZE];
+
+    stream_reader(file, &mut buffer, sink)
+}
+
+/// Feeds everything that can be read from `reader` using `buffer` into `sink`. UTF-8 sequences
+/// that are split across reads are carried over to the next read.
+fn stream_reader<R: Read, S: Sink>(
+    mut reader: R,
+    buffer: &mut [u8],
+    sink: &mut S,
+) -> Result<(), Error> {
+    let mut carry = 0;
+
+    loop {
+        let bytes_read = match reader.read(&mut buffer[carry..]) {
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(Error::from(e)),
+        };
+
+        let end = carry + bytes_read;
+
+        if bytes_read == 0 {
+            if carry > 0 {
+                return Err(Error::Binary);
+            }
+            return Ok(());
+        }
+
+        let chunk = &buffer[..end];
+
+        if chunk[carry..].contains(&0) {
+            return Err(Error::Binary);
+        }
+
+        let valid_up_to = match str::from_utf8(chunk) {
+            Ok(text) => {
+                sink.feed(text);
+                end
+            },
+            Err(e) if e.error_len().is_none() => {
+                let valid_up_to = e.valid_up_to();
+
+                if let Ok(text) = str::from_utf8(&chunk[..valid_up_to]) {
+                    sink.feed(text);
+                }
+
+                valid_up_to
+            },
+            Err(_) => return Err(Error::Binary),
+        };
+
+        buffer.copy_within(valid_up_to..end, 0);
+        carry = end - valid_up_to;
+    }
+}
+
+/// Computes the [Tally] of everything that can be read from `reader` using `buffer`.
+#[cfg(test)]
+fn tally_reader<R: Read>(reader: R, buffer: &mut [u8]) -> Result<Tally, Error> {
+    let mut cursor = Cursor::new();
+    stream_reader(reader, buffer, &mut cursor)?;
+    Ok(cursor.finish())
+}
+
+/// Feeds `text` into `sink` using `buffer`. Only used to test [Sink] implementations.
+#[cfg(test)]
+pub fn stream_str<S: Sink>(text: &str, buffer: &mut [u8], sink: &mut S) -> Result<(), Error> {
+    stream_reader(text.as_bytes(), buffer, sink)
+}
+
+#[test]
+fn test_tally() -> Result<(), Error> {
+    let tally = tally(Path::new("tests/data/nemesis.txt"))?;
+
+    assert_eq!(tally.lines, 4);
+    assert_eq!(tally.words, 27);
+
+    Ok(())
+}
+
+#[test]
+fn test_tally_across_reads() -> Result<(), Error> {
+    let text = "λ calculus\n\n  \t\nwith ünïcödé 🦀\nno trailing newline";
+
+    // A buffer this small guarantees that multi-byte characters are split across reads.
+    let mut buffer = [0; 4];
+    let tally = tally_reader(text.as_bytes(), &mut buffer)?;
+
+    let expected = Tally {
+        lines: u64::try_from(text.lines().count()).unwrap(),
+        nonblank_lines: 3,
+        words: u64::try_from(text.split_whitespace().count()).unwrap(),
+        chars: u64::try_from(text.chars().count()).unwrap(),
+    };
+
+    assert_eq!(tally, expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_tally_with() -> Result<(), Error> {
+    /// Collects everything that it's fed.
+    #[derive(Default)]
+    struct Collect(String);
+
+    impl Sink for Collect {
+        fn feed(&mut self, text: &str) {
+            self.0.push_str(text);
+        }
+    }
+
+    let path = Path::new("tests/data/nemesis.txt");
+
+    let mut collect = Collect::default();
+    let tally = tally_with(path, &mut collect)?;
+
+    assert_eq!(tally, self::tally(path)?);
+    assert_eq!(collect.0, std::fs::read_to_string(path)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_tally_binary() {
+    let mut buffer = [0; 8];
+
+    let nul = tally_reader(&b"text\0text"[..], &mut buffer);
+    assert!(matches!(nul, Err(Error::Binary)));
+
+    let invalid_utf8 = tally_reader(&b"text\xFF\xFEtext"[..], &mut buffer);
+    assert!(matches!(invalid_utf8, Err(Error::Binary)));
+
+    let truncated_utf8 = tally_reader(&"🦀".as_bytes()[..2], &mut buffer);
+    assert!(matches!(truncated_utf8, Err(Error::Binary)));
+}
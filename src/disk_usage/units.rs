@@ -15,6 +15,34 @@ pub enum PrefixKind {
     Si,
 }
 
+/// A unit that disk usage is always reported in regardless of magnitude, so that sizes can be
+/// easily compared with one another.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum FixedUnit {
+    #[value(name = "B")]
+    Base,
+    #[value(name = "KB")]
+    Kilo,
+    #[value(name = "MB")]
+    Mega,
+    #[value(name = "GB")]
+    Giga,
+    #[value(name = "TB")]
+    Tera,
+    #[value(name = "PB")]
+    Peta,
+    #[value(name = "KiB")]
+    Kibi,
+    #[value(name = "MiB")]
+    Mebi,
+    #[value(name = "GiB")]
+    Gibi,
+    #[value(name = "TiB")]
+    Tebi,
+    #[value(name = "PiB")]
+    Pebi,
+}
+
 /// Binary prefixes.
 #[derive(Debug, PartialEq, Eq)]
 pub enum BinPrefix {
@@ -23,6 +51,7 @@ pub enum BinPrefix {
     Mebi,
     Gibi,
     Tebi,
+    Pebi,
 }
 
 /// SI prefixes.
@@ -33,6 +62,7 @@ pub enum SiPrefix {
     Mega,
     Giga,
     Tera,
+    Peta,
 }
 
 impl SiPrefix {
@@ -44,6 +74,26 @@ impl SiPrefix {
             Self::Mega => "MB",
             Self::Giga => "GB",
             Self::Tera => "TB",
+            Self::Peta => "PB",
+        }
+    }
+}
+
+impl FixedUnit {
+    /// Returns the human readable representation of the fixed unit.
+    pub const fn as_str(&self) -> &str {
+        match self {
+            Self::Base => "B",
+            Self::Kilo => "KB",
+            Self::Mega => "MB",
+            Self::Giga => "GB",
+            Self::Tera => "TB",
+            Self::Peta => "PB",
+            Self::Kibi => "KiB",
+            Self::Mebi => "MiB",
+            Self::Gibi => "GiB",
+            Self::Tebi => "TiB",
+            Self::Pebi => "PiB",
         }
     }
 }
@@ -57,6 +107,7 @@ impl BinPrefix {
             Self::Mebi => "MiB",
             Self::Gibi => "GiB",
             Self::Tebi => "TiB",
+            Self::Pebi => "PiB",
         }
     }
 }
@@ -73,6 +124,7 @@ impl UnitPrefix for SiPrefix {
             Self::Mega => 10_u64.pow(6),
             Self::Giga => 10_u64.pow(9),
             Self::Tera => 10_u64.pow(12),
+            Self::Peta => 10_u64.pow(15),
         }
     }
 }
@@ -85,6 +137,25 @@ impl UnitPrefix for BinPrefix {
             Self::Mebi => 2_u64.pow(20),
             Self::Gibi => 2_u64.pow(30),
             Self::Tebi => 2_u64.pow(40),
+            Self::Pebi => 2_u64.pow(50),
+        }
+    }
+}
+
+impl UnitPrefix for FixedUnit {
+    fn base_value(&self) -> u64 {
+        match self {
+            Self::Base => 1,
+            Self::Kilo => SiPrefix::Kilo.base_value(),
+            Self::Mega => SiPrefix::Mega.base_value(),
+            Self::Giga => SiPrefix::Giga.base_value(),
+            Self::Tera => SiPrefix::Tera.base_value(),
+            Self::Peta => SiPrefix::Peta.base_value(),
+            Self::Kibi => BinPrefix::Kibi.base_value(),
+            Self::Mebi => BinPrefix::Mebi.base_value(),
+            Self::Gibi => BinPrefix::Gibi.base_value(),
+            Self::Tebi => BinPrefix::Tebi.base_value(),
+            Self::Pebi => BinPrefix::Pebi.base_value(),
         }
     }
 }
@@ -102,8 +173,10 @@ impl From<u64> for BinPrefix {
             Self::Mebi
         } else if log < 40. {
             Self::Gibi
-        } else {
+        } else if log < 50. {
             Self::Tebi
+        } else {
+            Self::Pebi
         }
     }
 }
@@ -121,8 +194,10 @@ impl From<u64> for SiPrefix {
             Self::Mega
         } else if log < 12. {
             Self::Giga
-        } else {
+        } else if log < 15. {
             Self::Tera
+        } else {
+            Self::Peta
         }
     }
 }
@@ -133,6 +208,12 @@ impl Display for BinPrefix {
     }
 }
 
+impl Display for FixedUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 impl Display for SiPrefix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
@@ -0,0 +1,133 @@
+use std::{
+    ffi::CStr,
+    fs::{File, OpenOptions},
+    io,
+    os::{
+        fd::{AsFd, AsRawFd, BorrowedFd},
+        unix::fs::OpenOptionsExt,
+    },
+    path::Path,
+};
+
+/// How many bytes of entries to ask `getdents64(2)` for at a time.
+const BATCH_SIZE: usize = 32 * 1024;
+
+/// Offset of `d_reclen`, the length of the whole record, within a `struct linux_dirent64`.
+const RECLEN_OFFSET: usize = 16;
+
+/// Offset of `d_type`, the type of the file if known, within a `struct linux_dirent64`.
+const TYPE_OFFSET: usize = 18;
+
+/// Offset of `d_name`, the nul-terminated file name, within a `struct linux_dirent64`.
+const NAME_OFFSET: usize = 19;
+
+/// A directory whose entries are read with `getdents64(2)` in large batches, without the per-entry
+/// bookkeeping of `readdir(3)`.
+pub struct Dir {
+    file: File,
+}
+
+/// The raw records of all of the entries of a [Dir].
+pub struct Entries {
+    buf: Vec<u8>,
+}
+
+/// Iterator over [Entries].
+pub struct Iter<'a> {
+    buf: &'a [u8],
+}
+
+/// An entry of a [Dir].
+pub struct Dirent<'a> {
+    name: &'a CStr,
+    d_type: u8,
+}
+
+impl Dir {
+    /// Opens the directory at `path`.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECTORY)
+            .open(path)
+            .map(|file| Self { file })
+    }
+
+    /// Reads all of the entries of the directory.
+    pub fn read(&self) -> io::Result<Entries> {
+        let mut buf = Vec::<u8>::with_capacity(BATCH_SIZE);
+
+        loop {
+            buf.reserve(BATCH_SIZE);
+            let spare = buf.spare_capacity_mut();
+
+            let read = unsafe {
+                libc::syscall(
+                    libc::SYS_getdents64,
+                    self.file.as_raw_fd(),
+                    spare.as_mut_ptr(),
+                    spare.len(),
+                )
+            };
+
+            match usize::try_from(read) {
+                Ok(0) => break,
+                Ok(read) => unsafe { buf.set_len(buf.len() + read) },
+                Err(_) => return Err(io::Error::last_os_error()),
+            }
+        }
+
+        Ok(Entries { buf })
+    }
+}
+
+impl AsFd for Dir {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.file.as_fd()
+    }
+}
+
+impl Entries {
+    /// Iterates over the entries, excluding `.` and `..`.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { buf: &self.buf }
+    }
+}
+
+impl<'a> Dirent<'a> {
+    /// The name of the file.
+    pub const fn name(&self) -> &'a CStr {
+        self.name
+    }
+
+    /// Whether the file is a directory. Returns `None` if the filesystem doesn't report the types
+    /// of files in directory entries.
+    pub const fn is_dir(&self) -> Option<bool> {
+        if self.d_type == libc::DT_UNKNOWN {
+            None
+        } else {
+            Some(self.d_type == libc::DT_DIR)
+        }
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Dirent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let reclen = self.buf.get(RECLEN_OFFSET..RECLEN_OFFSET + 2)?;
+            let reclen = usize::from(u16::from_ne_bytes([reclen[0], reclen[1]]));
+
+            let record = self.buf.get(..reclen).filter(|record| !record.is_empty())?;
+            self.buf = &self.buf[reclen..];
+
+            let d_type = *record.get(TYPE_OFFSET)?;
+            let name = CStr::from_bytes_until_nul(record.get(NAME_OFFSET..)?).ok()?;
+
+            if !matches!(name.to_bytes(), b"." | b"..") {
+                return Some(Dirent { name, d_type });
+            }
+        }
+    }
+}
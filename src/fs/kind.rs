@@ -0,0 +1,112 @@
+use ignore::DirEntry;
+use std::fs::Metadata;
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use std::path::Path;
+
+/// Kinds of files that are distinguished from regular files when rendering and counting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Dir,
+    Symlink,
+    BrokenLink,
+    Socket,
+    Fifo,
+    BlockDevice,
+    CharDevice,
+    Executable,
+}
+
+impl Kind {
+    /// Every [Kind] in the order that their names are listed in error messages.
+    const ALL: [Self; 8] = [
+        Self::Dir,
+        Self::Symlink,
+        Self::BrokenLink,
+        Self::Socket,
+        Self::Fifo,
+        Self::BlockDevice,
+        Self::CharDevice,
+        Self::Executable,
+    ];
+
+    /// Determines the [Kind] of the file, if any, using its `metadata` which is expected to come
+    /// from the provided `entry`.
+    pub fn of(entry: &DirEntry, metadata: &Metadata) -> Option<Self> {
+        let ft = entry.file_type()?;
+
+        if ft.is_dir() {
+            return Some(Self::Dir);
+        } else if ft.is_symlink() {
+            if entry.path().exists() {
+                return Some(Self::Symlink);
+            }
+            return Some(Self::BrokenLink);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+            if ft.is_socket() {
+                return Some(Self::Socket);
+            } else if ft.is_fifo() {
+                return Some(Self::Fifo);
+            } else if ft.is_block_device() {
+                return Some(Self::BlockDevice);
+            } else if ft.is_char_device() {
+                return Some(Self::CharDevice);
+            } else if ft.is_file() && metadata.permissions().mode() & 0o111 != 0 {
+                return Some(Self::Executable);
+            }
+        }
+
+        #[cfg(windows)]
+        let _ = metadata;
+
+        None
+    }
+
+    /// Determines the [Kind] of the file at `path` from its raw `st_mode`.
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    pub fn of_mode(path: &Path, mode: u32) -> Option<Self> {
+        use libc::{S_IFBLK, S_IFCHR, S_IFDIR, S_IFIFO, S_IFLNK, S_IFMT, S_IFREG, S_IFSOCK};
+
+        match mode & S_IFMT {
+            S_IFDIR => Some(Self::Dir),
+            S_IFLNK if path.exists() => Some(Self::Symlink),
+            S_IFLNK => Some(Self::BrokenLink),
+            S_IFSOCK => Some(Self::Socket),
+            S_IFIFO => Some(Self::Fifo),
+            S_IFBLK => Some(Self::BlockDevice),
+            S_IFCHR => Some(Self::CharDevice),
+            S_IFREG if mode & 0o111 != 0 => Some(Self::Executable),
+            _ => None,
+        }
+    }
+
+    /// The name of the [Kind] as it appears in configuration.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Dir => "dir",
+            Self::Symlink => "symlink",
+            Self::BrokenLink => "broken_link",
+            Self::Socket => "socket",
+            Self::Fifo => "fifo",
+            Self::BlockDevice => "block_device",
+            Self::CharDevice => "char_device",
+            Self::Executable => "executable",
+        }
+    }
+
+    /// Parses a [Kind] from its name.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name).join(", ");
+                format!("unknown file type '{name}'; expected one of {names}")
+            })
+    }
+}
@@ -0,0 +1,196 @@
+use ignore::Error as IgnoreError;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// The most symlinks that will be resolved in a single chain before giving up, mirroring
+/// `MAXSYMLINKS` on Linux.
+const MAX_HOPS: usize = 40;
+
+/// The full resolution chain of a symlink, i.e. `a -> b -> c`.
+#[derive(Debug)]
+pub struct Chain {
+    /// The targets of each symlink in the chain exactly as they are stored on disk.
+    hops: Vec<PathBuf>,
+
+    /// How resolution of the chain concluded.
+    end: End,
+
+    /// Whether or not the final target lies outside of the root of the traversal.
+    escapes_root: bool,
+}
+
+/// How the resolution of a [Chain] concluded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum End {
+    /// The final target exists.
+    Resolved,
+
+    /// The final target does not exist.
+    Broken,
+
+    /// A symlink in the chain refers back to an earlier one.
+    Cycle,
+
+    /// The final target is a directory containing the symlink, so following it never terminates.
+    Ancestor,
+}
+
+impl Chain {
+    /// Resolves the chain of symlinks starting at `link`, one hop at a time. `root` is expected to
+    /// be the canonical path of the root of the traversal.
+    pub fn resolve(link: &Path, root: &Path) -> Self {
+        let mut hops = vec![];
+        let mut visited = HashSet::from([normalize(link)]);
+        let mut current = link.to_path_buf();
+
+        let (end, last) = loop {
+            let Ok(target) = fs::read_link(&current) else {
+                break (End::Broken, current);
+            };
+
+            let parent = current.parent().unwrap_or_else(|| Path::new(""));
+            let next = normalize(&parent.join(&target));
+            hops.push(target);
+
+            if !visited.insert(next.clone()) || hops.len() >= MAX_HOPS {
+                break (End::Cycle, next);
+            }
+
+            match fs::symlink_metadata(&next) {
+                Ok(md) if md.file_type().is_symlink() => current = next,
+                Ok(md) => {
+                    let ancestor = md.is_dir()
+                        && fs::canonicalize(&next).is_ok_and(|target| {
+                            link.parent()
+                                .and_then(|parent| fs::canonicalize(parent).ok())
+                                .is_some_and(|parent| parent.starts_with(target))
+                        });
+
+                    let end = if ancestor {
+                        End::Ancestor
+                    } else {
+                        End::Resolved
+                    };
+                    break (end, next);
+                },
+                Err(_) => break (End::Broken, next),
+            }
+        };
+
+        let escapes_root = !fs::canonicalize(&last).unwrap_or(last).starts_with(root);
+
+        Self {
+            hops,
+            end,
+            escapes_root,
+        }
+    }
+
+    /// The targets of each symlink in the chain exactly as they are stored on disk.
+    pub fn hops(&self) -> &[PathBuf] {
+        &self.hops
+    }
+
+    /// How resolution of the chain concluded.
+    pub const fn end(&self) -> End {
+        self.end
+    }
+
+    /// Annotations describing anything notable about the chain.
+    pub fn flags(&self) -> Vec<&'static str> {
+        let mut flags = vec![];
+
+        match self.end {
+            End::Resolved => (),
+            End::Broken => flags.push("broken"),
+            End::Cycle => flags.push("cycle"),
+            End::Ancestor => flags.push("loops to ancestor"),
+        }
+
+        if self.escapes_root {
+            flags.push("outside root");
+        }
+
+        flags
+    }
+}
+
+/// The path of the file that an error pertains to, if any.
+pub fn error_path(err: &IgnoreError) -> Option<&Path> {
+    match err {
+        IgnoreError::WithPath { path, .. } => Some(path),
+        IgnoreError::Loop { child, .. } => Some(child),
+        IgnoreError::WithDepth { err, .. } | IgnoreError::WithLineNumber { err, .. } => {
+            error_path(err)
+        },
+        _ => None,
+    }
+}
+
+/// Lexically resolves `.` and `..` components without touching the disk.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            },
+            _ => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+#[cfg(unix)]
+#[test]
+fn test_resolve_chain() -> std::io::Result<()> {
+    use std::os::unix::fs::symlink;
+
+    let tmp = tempfile::TempDir::new()?;
+    let root = fs::canonicalize(tmp.path())?;
+
+    fs::create_dir(root.join("dir"))?;
+    fs::File::create(root.join("file"))?;
+    symlink("b", root.join("a"))?;
+    symlink("file", root.join("b"))?;
+    symlink("/dev/null", root.join("abs"))?;
+    symlink("y", root.join("x"))?;
+    symlink("x", root.join("y"))?;
+    symlink("..", root.join("dir").join("up"))?;
+
+    let chain = Chain::resolve(&root.join("a"), &root);
+    assert_eq!(chain.hops(), [PathBuf::from("b"), PathBuf::from("file")]);
+    assert_eq!(chain.end(), End::Resolved);
+    assert!(chain.flags().is_empty());
+
+    let chain = Chain::resolve(&root.join("abs"), &root);
+    assert!(chain.hops()[0].is_absolute());
+    assert_eq!(chain.flags(), ["outside root"]);
+
+    let chain = Chain::resolve(&root.join("x"), &root);
+    assert_eq!(chain.end(), End::Cycle);
+    assert_eq!(chain.hops().len(), 2);
+
+    let chain = Chain::resolve(&root.join("dir").join("up"), &root);
+    assert_eq!(chain.end(), End::Ancestor);
+    assert_eq!(chain.flags(), ["loops to ancestor"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(
+        normalize(Path::new("/a/b/../c/./d")),
+        PathBuf::from("/a/c/d")
+    );
+    assert_eq!(normalize(Path::new("../a")), PathBuf::from("../a"));
+}
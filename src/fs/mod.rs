@@ -1,9 +1,26 @@
 use ignore::DirEntry;
 use std::{fs, path::PathBuf};
 
+/// Reading the entries of directories in batches with `getdents64(2)`.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub mod getdents;
+
 /// Operations pertaining to underlying inodes of files.
 pub mod inode;
 
+/// Resolution of symlink chains along with detection of cycles and targets outside of the root.
+pub mod link;
+
+/// Classification of special files such as sockets, devices, and broken symlinks.
+pub mod kind;
+
+/// Mounted filesystems as listed in `/proc/self/mountinfo`.
+pub mod mount;
+
+/// Querying only the parts of a file's status that are needed with `statx(2)`.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub mod statx;
+
 /// Unix file permissions.
 #[cfg(unix)]
 pub mod permissions;
@@ -0,0 +1,139 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+/// Lazily evaluated static that contains every mount of the current process keyed by mount point.
+static MOUNTS: OnceLock<HashMap<PathBuf, Mount>> = OnceLock::new();
+
+/// A mounted filesystem.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Mount {
+    /// Where the filesystem is mounted.
+    pub path: PathBuf,
+
+    /// The type of filesystem e.g. `ext4` or `tmpfs`.
+    pub fs_type: String,
+
+    /// The device or other source of the filesystem e.g. `/dev/sda1`.
+    pub source: String,
+}
+
+/// A directory that is a [Mount] point as encountered during traversal.
+#[derive(Clone, Copy, Debug)]
+pub struct MountPoint {
+    /// The filesystem mounted on the directory.
+    pub mount: &'static Mount,
+
+    /// Whether or not traversal descended into the mounted filesystem, which isn't the case for
+    /// mounts on a different filesystem than the root when `--one-file-system` is enabled.
+    pub crossed: bool,
+}
+
+/// Returns the [Mount] whose mount point is exactly `path`.
+pub fn lookup(path: &Path) -> Option<&'static Mount> {
+    MOUNTS.get_or_init(read).get(path)
+}
+
+/// Returns the [Mount] of the filesystem that `path` resides on, i.e. the one mounted on its
+/// nearest ancestor.
+pub fn containing(path: &Path) -> Option<&'static Mount> {
+    path.ancestors().find_map(lookup)
+}
+
+/// Reads the mounts of the current process from `/proc/self/mountinfo`. Mounts are unavailable
+/// on platforms without it.
+fn read() -> HashMap<PathBuf, Mount> {
+    std::fs::read_to_string("/proc/self/mountinfo")
+        .map(|contents| parse(&contents))
+        .unwrap_or_default()
+}
+
+/// Parses the contents of `/proc/self/mountinfo`, a line of which looks like the following:
+///
+/// ```text
+/// 36 35 98:0 /mnt1 /mnt/parent rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+/// ```
+///
+/// The fifth field is the mount point and the two fields following the `-` separator are the
+/// filesystem type and source. Mounts that are stacked on top of an earlier mount at the same
+/// mount point take its place.
+fn parse(contents: &str) -> HashMap<PathBuf, Mount> {
+    let mut mounts = HashMap::new();
+
+    for line in contents.lines() {
+        let Some((fields, rest)) = line.split_once(" - ") else {
+            continue;
+        };
+
+        let (Some(mount_point), Some(fs_type), Some(source)) = (
+            fields.split(' ').nth(4),
+            rest.split(' ').next(),
+            rest.split(' ').nth(1),
+        ) else {
+            continue;
+        };
+
+        let mount_point = PathBuf::from(unescape(mount_point));
+
+        let mount = Mount {
+            path: mount_point.clone(),
+            fs_type: unescape(fs_type),
+            source: unescape(source),
+        };
+
+        mounts.insert(mount_point, mount);
+    }
+
+    mounts
+}
+
+/// Whitespace and backslashes are escaped as three octal digits preceded by a backslash.
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut rest = field;
+
+    while let Some(i) = rest.find('\\') {
+        unescaped.push_str(&rest[..i]);
+
+        let octal = rest.get(i + 1..i + 4);
+
+        if let Some(byte) = octal.and_then(|o| u8::from_str_radix(o, 8).ok()) {
+            unescaped.push(char::from(byte));
+            rest = &rest[i + 4..];
+        } else {
+            unescaped.push('\\');
+            rest = &rest[i + 1..];
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+impl Display for Mount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.fs_type, self.source)
+    }
+}
+
+#[test]
+fn test_parse() {
+    let mounts = parse(
+        "22 1 0:21 / / rw,relatime - overlay overlay rw,lowerdir=/a\n\
+         23 22 0:22 / /proc rw,relatime - proc proc rw\n\
+         36 22 8:1 /data /mnt/my\\040disk rw,noatime shared:1 master:2 - ext4 /dev/sda1 rw\n\
+         37 23 0:23 / /proc rw - tmpfs tmpfs rw\n\
+         garbage",
+    );
+
+    assert_eq!(mounts.len(), 3);
+    assert_eq!(mounts[Path::new("/")].fs_type, "overlay");
+    assert_eq!(mounts[Path::new("/proc")].fs_type, "tmpfs");
+
+    let disk = &mounts[Path::new("/mnt/my disk")];
+    assert_eq!(disk.source, "/dev/sda1");
+    assert_eq!(disk.to_string(), "ext4 /dev/sda1");
+}
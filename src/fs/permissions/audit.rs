@@ -0,0 +1,160 @@
+use super::FileMode;
+use std::fmt::{self, Display};
+
+/// Properties of a file that warrant attention during a security review.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Finding {
+    Setuid,
+    Setgid,
+    Sticky,
+    WorldWritable,
+
+    /// Linux capabilities that are granted to the file when it's executed.
+    Capabilities,
+}
+
+/// The set of [Finding]s that apply to a single file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Findings(u8);
+
+/// Tallies of each [Finding] across many files.
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+    counts: [usize; Finding::ALL.len()],
+}
+
+impl Finding {
+    /// Every kind of [Finding] in the order that they're displayed.
+    pub const ALL: [Self; 5] = [
+        Self::Setuid,
+        Self::Setgid,
+        Self::Capabilities,
+        Self::WorldWritable,
+        Self::Sticky,
+    ];
+
+    /// Human-readable name of the [Finding].
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Setuid => "setuid",
+            Self::Setgid => "setgid",
+            Self::Sticky => "sticky",
+            Self::WorldWritable => "world-writable",
+            Self::Capabilities => "capabilities",
+        }
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl Findings {
+    /// Inspects the [`FileMode`] of a file along with whether or not it has capabilities.
+    pub fn of(mode: &FileMode, has_capabilities: bool) -> Self {
+        let checks = [
+            (Finding::Setuid, mode.is_setuid()),
+            (Finding::Setgid, mode.is_setgid()),
+            (Finding::Sticky, mode.is_sticky()),
+            (Finding::WorldWritable, mode.is_world_writable()),
+            (Finding::Capabilities, has_capabilities),
+        ];
+
+        checks
+            .into_iter()
+            .filter(|(_, flagged)| *flagged)
+            .fold(Self::empty(), |Self(bits), (finding, _)| {
+                Self(bits | finding.bit())
+            })
+    }
+
+    /// A file about which nothing was flagged.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns `true` if nothing about the file was flagged.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if `finding` applies to the file.
+    pub const fn contains(self, finding: Finding) -> bool {
+        self.0 & finding.bit() != 0
+    }
+
+    /// Iterates over the [Finding]s that apply to the file.
+    pub fn iter(self) -> impl Iterator<Item = Finding> {
+        Finding::ALL
+            .into_iter()
+            .filter(move |finding| self.contains(*finding))
+    }
+}
+
+impl Display for Findings {
+    /// Comma-separated names of each [Finding].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self.iter().map(Finding::name).collect::<Vec<_>>();
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+impl Summary {
+    /// Accounts for the [Findings] of a single file.
+    pub fn add(&mut self, findings: Findings) {
+        for (i, finding) in Finding::ALL.into_iter().enumerate() {
+            if findings.contains(finding) {
+                self.counts[i] += 1;
+            }
+        }
+    }
+}
+
+impl Display for Summary {
+    /// e.g. `2 setuid, 1 world-writable` or a note that nothing was flagged.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tallies = Finding::ALL
+            .into_iter()
+            .zip(self.counts)
+            .filter(|(_, count)| *count > 0)
+            .map(|(finding, count)| match finding {
+                Finding::Capabilities => format!("{count} with capabilities"),
+                _ => format!("{count} {}", finding.name()),
+            })
+            .collect::<Vec<_>>();
+
+        if tallies.is_empty() {
+            return write!(f, "no privileged or world-writable files");
+        }
+
+        write!(f, "{}", tallies.join(", "))
+    }
+}
+
+#[test]
+fn test_findings() -> Result<(), super::error::Error> {
+    use std::convert::TryFrom;
+
+    let setuid = FileMode::try_from(0o104_755)?;
+    let findings = Findings::of(&setuid, true);
+    assert!(findings.contains(Finding::Setuid));
+    assert!(!findings.contains(Finding::Setgid));
+    assert_eq!(findings.to_string(), "setuid, capabilities");
+
+    let tmp = FileMode::try_from(0o041_777)?;
+    let regular = FileMode::try_from(0o100_644)?;
+    assert!(Findings::of(&regular, false).is_empty());
+
+    let mut summary = Summary::default();
+    assert_eq!(summary.to_string(), "no privileged or world-writable files");
+
+    summary.add(findings);
+    summary.add(Findings::of(&tmp, false));
+    summary.add(Findings::of(&regular, false));
+    assert_eq!(
+        summary.to_string(),
+        "1 setuid, 1 with capabilities, 1 world-writable, 1 sticky"
+    );
+
+    Ok(())
+}
@@ -26,6 +26,14 @@ pub enum Attribute {
     Sticky,
 }
 
+/// A single kind of access that a class may be granted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
 /// Read, write, execute permissions.
 #[derive(Debug, PartialEq, Eq)]
 pub enum PermissionsTriad {
@@ -82,9 +90,44 @@ impl Permissions {
 
     /// Returns `true` if sticky bit is enabled.
     pub fn attr_is_sticky(&self) -> bool {
-        self.attr
-            .as_ref()
-            .map_or(false, |attr| attr == &Attribute::Sticky)
+        self.attr == Some(Attribute::Sticky)
+    }
+
+    /// Returns `true` if the special `attr` is enabled.
+    pub fn has_attr(&self, attr: &Attribute) -> bool {
+        self.attr.as_ref() == Some(attr)
+    }
+
+    /// Returns `true` if the class is permitted to write.
+    pub const fn is_writable(&self) -> bool {
+        matches!(
+            self.triad,
+            PermissionsTriad::Write
+                | PermissionsTriad::ReadWrite
+                | PermissionsTriad::WriteExecute
+                | PermissionsTriad::ReadWriteExecute
+        )
+    }
+
+    /// Returns `true` if the class is granted `access`.
+    pub const fn allows(&self, access: Access) -> bool {
+        match access {
+            Access::Read => matches!(
+                self.triad,
+                PermissionsTriad::Read
+                    | PermissionsTriad::ReadWrite
+                    | PermissionsTriad::ReadExecute
+                    | PermissionsTriad::ReadWriteExecute
+            ),
+            Access::Write => self.is_writable(),
+            Access::Execute => matches!(
+                self.triad,
+                PermissionsTriad::Execute
+                    | PermissionsTriad::ReadExecute
+                    | PermissionsTriad::WriteExecute
+                    | PermissionsTriad::ReadWriteExecute
+            ),
+        }
     }
 
     /// Helper function to compute permissions.
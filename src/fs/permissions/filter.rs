@@ -0,0 +1,157 @@
+use super::FileMode;
+use std::str::FromStr;
+
+/// Permission bits that may be specified with a [`ModeFilter`], i.e. everything save for the
+/// file-type.
+const PERMISSION_BITS: u32 = 0o7777;
+
+/// Criteria that the permissions of a file must meet, specified in the manner of `find -perm`.
+/// The mode is either octal e.g. `755` or a comma-separated list of symbolic clauses e.g.
+/// `u+x,o+w`, optionally prefixed by `-` to require at least all of its bits or `/` to require
+/// any one of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModeFilter {
+    bits: u32,
+    kind: Kind,
+}
+
+/// How the bits of a [`ModeFilter`] are compared to the permissions of a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    /// Permissions are exactly the specified bits.
+    Exact,
+
+    /// All of the specified bits are set.
+    All,
+
+    /// Any of the specified bits is set.
+    Any,
+}
+
+impl ModeFilter {
+    /// Returns `true` if the permissions of `mode` meet the criteria.
+    pub const fn matches(self, mode: &FileMode) -> bool {
+        let bits = mode.st_mode & PERMISSION_BITS;
+
+        match self.kind {
+            Kind::Exact => bits == self.bits,
+            Kind::All => bits & self.bits == self.bits,
+            Kind::Any => self.bits == 0 || bits & self.bits != 0,
+        }
+    }
+}
+
+impl FromStr for ModeFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, mode) = match s.as_bytes().first() {
+            Some(b'-') => (Kind::All, &s[1..]),
+            Some(b'/') => (Kind::Any, &s[1..]),
+            _ => (Kind::Exact, s),
+        };
+
+        let bits = if mode.starts_with(|ch: char| ch.is_ascii_digit()) {
+            u32::from_str_radix(mode, 8)
+                .ok()
+                .filter(|bits| bits & !PERMISSION_BITS == 0)
+                .ok_or_else(|| format!("Invalid octal mode '{mode}'"))?
+        } else {
+            parse_symbolic(mode)?
+        };
+
+        Ok(Self { bits, kind })
+    }
+}
+
+/// Applies each clause of a symbolic mode such as `u+rwx,go=rx` to an initially empty set of
+/// permissions.
+fn parse_symbolic(mode: &str) -> Result<u32, String> {
+    let invalid = || format!("Invalid symbolic mode '{mode}'");
+
+    let mut bits = 0;
+
+    for clause in mode.split(',') {
+        let op_index = clause.find(['+', '-', '=']).ok_or_else(invalid)?;
+        let (who, rest) = clause.split_at(op_index);
+
+        let mut who_mask = 0;
+
+        for ch in who.chars() {
+            who_mask |= match ch {
+                'u' => 0o4700,
+                'g' => 0o2070,
+                'o' => 0o1007,
+                'a' => 0o7777,
+                _ => return Err(invalid()),
+            };
+        }
+
+        // No classes means all of them, as with chmod.
+        if who_mask == 0 {
+            who_mask = 0o7777;
+        }
+
+        let mut perm_mask = 0;
+
+        for ch in rest[1..].chars() {
+            perm_mask |= match ch {
+                'r' => 0o444,
+                'w' => 0o222,
+                'x' => 0o111,
+                's' => 0o6000,
+                't' => 0o1000,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let clause_bits = who_mask & perm_mask;
+
+        match &rest[..1] {
+            "+" => bits |= clause_bits,
+            "-" => bits &= !clause_bits,
+            _ => bits = (bits & !who_mask) | clause_bits,
+        }
+    }
+
+    Ok(bits)
+}
+
+#[test]
+fn test_mode_filter() -> Result<(), Box<dyn std::error::Error>> {
+    use std::convert::TryFrom;
+
+    let regular = FileMode::try_from(0o100_755)?;
+    let shared = FileMode::try_from(0o100_666)?;
+    let setuid_exec = FileMode::try_from(0o104_755)?;
+
+    let exact = "755".parse::<ModeFilter>()?;
+    assert!(exact.matches(&regular));
+    assert!(!exact.matches(&setuid_exec));
+
+    let other_writable = "-o+w".parse::<ModeFilter>()?;
+    assert!(other_writable.matches(&shared));
+    assert!(!other_writable.matches(&regular));
+
+    let setuid = "-u+s".parse::<ModeFilter>()?;
+    assert!(setuid.matches(&setuid_exec));
+    assert!(!setuid.matches(&regular));
+
+    let any_exec = "/a+x".parse::<ModeFilter>()?;
+    assert!(any_exec.matches(&regular));
+    assert!(!any_exec.matches(&shared));
+
+    assert_eq!(
+        "u=rwx,go=rx".parse::<ModeFilter>()?,
+        "755".parse::<ModeFilter>()?
+    );
+    assert_eq!("a+rw,o-w".parse::<ModeFilter>()?, "664".parse()?);
+
+    assert!("19".parse::<ModeFilter>().is_err());
+    assert!("17777".parse::<ModeFilter>().is_err());
+    assert!("z+w".parse::<ModeFilter>().is_err());
+    assert!("u+q".parse::<ModeFilter>().is_err());
+    assert!("uw".parse::<ModeFilter>().is_err());
+
+    Ok(())
+}
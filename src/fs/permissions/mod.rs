@@ -1,3 +1,4 @@
+use super::ug::Account;
 use error::Error;
 use file_type::FileType;
 use std::{
@@ -6,12 +7,18 @@ use std::{
     os::unix::fs::PermissionsExt,
 };
 
+/// Flagging of privileged and world-writable files for security reviews.
+pub mod audit;
+
 /// For working with permissions for a particular class i.e. user, group, or other.
 pub mod class;
 
 /// File permission related errors.
 pub mod error;
 
+/// Filtering files by their permissions.
+pub mod filter;
+
 /// For working with Unix file identifiers.
 pub mod file_type;
 
@@ -83,6 +90,47 @@ impl FileMode {
     pub const fn other_permissions(&self) -> &class::Permissions {
         &self.other_permissions
     }
+
+    /// Returns `true` if the file runs with the privileges of its owner.
+    pub fn is_setuid(&self) -> bool {
+        self.user_permissions.has_attr(&class::Attribute::SUID)
+    }
+
+    /// Returns `true` if the file runs with the privileges of its group or, in the case of
+    /// directories, if new files inherit the group of the directory.
+    pub fn is_setgid(&self) -> bool {
+        self.group_permissions.has_attr(&class::Attribute::SGID)
+    }
+
+    /// Returns `true` if the sticky bit is set.
+    pub fn is_sticky(&self) -> bool {
+        self.other_permissions.has_attr(&class::Attribute::Sticky)
+    }
+
+    /// Returns `true` if anyone may write to the file. Symlinks are disregarded as their
+    /// permissions are meaningless.
+    pub fn is_world_writable(&self) -> bool {
+        self.file_type != FileType::Symlink && self.other_permissions.is_writable()
+    }
+
+    /// Returns `true` if `account` is granted `access` to a file owned by `uid` and `gid`. Only the
+    /// permissions of the most specific class that `account` belongs to apply, save for the
+    /// superuser who may read and write anything and execute anything executable by someone.
+    pub fn grants(&self, access: class::Access, account: &Account, uid: u32, gid: u32) -> bool {
+        if account.is_superuser() {
+            return access != class::Access::Execute
+                || self.file_type == FileType::Directory
+                || self.st_mode & 0o111 != 0;
+        }
+
+        if account.uid == uid {
+            self.user_permissions.allows(access)
+        } else if account.gids.contains(&gid) {
+            self.group_permissions.allows(access)
+        } else {
+            self.other_permissions.allows(access)
+        }
+    }
 }
 
 /// For representing [`FileMode`] in symbolic notation.
@@ -110,6 +158,8 @@ impl Display for FileModeXAttrs<'_> {
 
 /// For the octal representation of permissions
 impl Octal for FileMode {
+    // The file-type bits are a `u16` on some platforms such as macOS.
+    #[allow(clippy::useless_conversion)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let modes_mask = self.st_mode & !u32::from(libc::S_IFMT);
         fmt::Octal::fmt(&modes_mask, f)
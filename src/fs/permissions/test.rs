@@ -94,3 +94,22 @@ fn test_symbolic_notation_special_attr() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_special_modes() -> Result<(), Box<dyn Error>> {
+    use super::FileMode;
+
+    let file_mode = FileMode::try_from(0o104_755)?;
+    assert!(file_mode.is_setuid());
+    assert!(!file_mode.is_setgid());
+    assert!(!file_mode.is_world_writable());
+
+    let file_mode = FileMode::try_from(0o041_777)?;
+    assert!(file_mode.is_sticky());
+    assert!(file_mode.is_world_writable());
+
+    let file_mode = FileMode::try_from(0o120_777)?;
+    assert!(!file_mode.is_world_writable());
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use libc::{statx_timestamp, AT_FDCWD, AT_SYMLINK_NOFOLLOW};
+use std::{
+    ffi::CStr,
+    io,
+    mem::MaybeUninit,
+    os::fd::{AsRawFd, BorrowedFd},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The fields that are always queried: the type and permissions of the file as well as what's
+/// needed to tell whether it's a hard link that was already accounted for.
+pub const BASIC: u32 = libc::STATX_TYPE | libc::STATX_MODE | libc::STATX_INO | libc::STATX_NLINK;
+
+/// The logical size of the file.
+pub const SIZE: u32 = libc::STATX_SIZE;
+
+/// The number of 512-byte blocks allocated to the file.
+pub const BLOCKS: u32 = libc::STATX_BLOCKS;
+
+/// The owner and group of the file.
+pub const OWNERSHIP: u32 = libc::STATX_UID | libc::STATX_GID;
+
+/// All of the timestamps of the file.
+pub const TIMES: u32 =
+    libc::STATX_ATIME | libc::STATX_MTIME | libc::STATX_CTIME | libc::STATX_BTIME;
+
+/// The status of a file as reported by `statx(2)`. Only the fields that were asked for are
+/// guaranteed to be filled in; filesystems may leave out the rest.
+pub struct Statx(libc::statx);
+
+impl Statx {
+    /// Queries the fields of `mask` for the file named `name` in the directory `dir`, or for the
+    /// file at the path `name` if no `dir` is provided. Symlinks aren't followed.
+    pub fn at(dir: Option<BorrowedFd<'_>>, name: &CStr, mask: u32) -> io::Result<Self> {
+        let dirfd = dir.map_or(AT_FDCWD, |fd| fd.as_raw_fd());
+        let mut buf = MaybeUninit::<libc::statx>::zeroed();
+
+        let ret = unsafe {
+            libc::statx(
+                dirfd,
+                name.as_ptr(),
+                AT_SYMLINK_NOFOLLOW,
+                mask,
+                buf.as_mut_ptr(),
+            )
+        };
+
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self(unsafe { buf.assume_init() }))
+    }
+
+    /// The raw `st_mode` of the file which includes its type.
+    pub fn mode(&self) -> u32 {
+        u32::from(self.0.stx_mode)
+    }
+
+    /// The type of the file as one of the `S_IF*` constants.
+    pub fn format(&self) -> u32 {
+        self.mode() & libc::S_IFMT
+    }
+
+    /// Returns `true` if the file is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.format() == libc::S_IFDIR
+    }
+
+    /// Returns `true` if the file is a regular file.
+    pub fn is_file(&self) -> bool {
+        self.format() == libc::S_IFREG
+    }
+
+    /// Returns `true` if the file is a symlink.
+    pub fn is_symlink(&self) -> bool {
+        self.format() == libc::S_IFLNK
+    }
+
+    /// The logical size of the file in bytes.
+    pub const fn len(&self) -> u64 {
+        self.0.stx_size
+    }
+
+    /// The number of 512-byte blocks allocated to the file.
+    pub const fn blocks(&self) -> u64 {
+        self.0.stx_blocks
+    }
+
+    /// The inode number of the file.
+    pub const fn ino(&self) -> u64 {
+        self.0.stx_ino
+    }
+
+    /// The id of the device that contains the file.
+    pub const fn dev(&self) -> u64 {
+        libc::makedev(self.0.stx_dev_major, self.0.stx_dev_minor)
+    }
+
+    /// The number of hard links to the file.
+    pub fn nlink(&self) -> u64 {
+        u64::from(self.0.stx_nlink)
+    }
+
+    /// The user id of the owner of the file.
+    pub const fn uid(&self) -> u32 {
+        self.0.stx_uid
+    }
+
+    /// The group id of the file.
+    pub const fn gid(&self) -> u32 {
+        self.0.stx_gid
+    }
+
+    /// Timestamp of when the file was last modified.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.time(libc::STATX_MTIME, &self.0.stx_mtime)
+    }
+
+    /// Timestamp of when the file was created, which not all filesystems keep track of.
+    pub fn created(&self) -> Option<SystemTime> {
+        self.time(libc::STATX_BTIME, &self.0.stx_btime)
+    }
+
+    /// Timestamp of when the file was last accessed.
+    pub fn accessed(&self) -> Option<SystemTime> {
+        self.time(libc::STATX_ATIME, &self.0.stx_atime)
+    }
+
+    /// Timestamp of when the file's status, i.e. its inode, was last changed.
+    pub fn changed(&self) -> Option<SystemTime> {
+        self.time(libc::STATX_CTIME, &self.0.stx_ctime)
+    }
+
+    /// Converts `timestamp` into a [`SystemTime`] if the `field` it belongs to was filled in.
+    fn time(&self, field: u32, timestamp: &statx_timestamp) -> Option<SystemTime> {
+        if self.0.stx_mask & field == 0 {
+            return None;
+        }
+
+        let secs = timestamp.tv_sec;
+        let nanos = timestamp.tv_nsec;
+
+        match u64::try_from(secs) {
+            Ok(secs) => UNIX_EPOCH.checked_add(Duration::new(secs, nanos)),
+            Err(_) => UNIX_EPOCH
+                .checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+                .checked_add(Duration::from_nanos(u64::from(nanos))),
+        }
+    }
+}
@@ -1,32 +1,41 @@
-use errno::{errno, set_errno, Errno};
-use std::{ffi::CStr, fs::Metadata, os::unix::fs::MetadataExt};
+use errno::Errno;
+use libc::{c_char, c_int};
+use std::{
+    collections::HashMap,
+    ffi::{CStr, CString},
+    fs::Metadata,
+    mem::MaybeUninit,
+    os::unix::fs::MetadataExt,
+    ptr,
+    sync::{Arc, OnceLock, RwLock},
+};
 
-type Owner = String;
-type Group = String;
+type Owner = Arc<str>;
+type Group = Arc<str>;
 
 impl UserGroupInfo for Metadata {}
 
 /// Trait that allows for files to query their owner and group.
 pub trait UserGroupInfo: MetadataExt {
-    /// Attemps to query the owner of the implementor.
-    fn try_get_owner(&self) -> Result<String, Error> {
-        unsafe {
-            let uid = self.uid();
-            try_get_user(uid)
-        }
+    /// Queries both the owner and group of the implementor, falling back to their numeric ids if
+    /// they don't exist such as after an account is removed. Names aren't queried at all if
+    /// `numeric` is `true`.
+    fn owner_and_group(&self, numeric: bool) -> (Owner, Group) {
+        owner_and_group(self.uid(), self.gid(), numeric)
     }
+}
 
-    /// Attempts to query both the owner and group of the implementor.
-    fn try_get_owner_and_group(&self) -> Result<(Owner, Group), Error> {
-        unsafe {
-            let uid = self.uid();
-            let gid = self.gid();
-            let user = try_get_user(uid)?;
-            let group = try_get_group(gid)?;
-
-            Ok((user, group))
-        }
+/// The names of the user `uid` and the group `gid`, falling back to their numeric ids if they
+/// don't exist. Names aren't queried at all if `numeric` is `true`.
+pub fn owner_and_group(uid: u32, gid: u32, numeric: bool) -> (Owner, Group) {
+    if numeric {
+        return (numeric_id(uid), numeric_id(gid));
     }
+
+    let user = user_name(uid).unwrap_or_else(|| numeric_id(uid));
+    let group = group_name(gid).unwrap_or_else(|| numeric_id(gid));
+
+    (user, group)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -41,44 +50,270 @@ pub enum Error {
     InvalidGroup,
 }
 
-/// Attempts to return the name of the group associated with `gid`.
-unsafe fn try_get_group(gid: libc::gid_t) -> Result<String, Error> {
-    set_errno(Errno(0));
+/// A user along with every group that they belong to, for determining what they may access.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Account {
+    pub uid: u32,
+    pub gids: Vec<u32>,
+}
+
+impl Account {
+    /// The effective user and groups of the current process.
+    pub fn current() -> Self {
+        unsafe {
+            let uid = libc::geteuid();
+            let mut gids = vec![libc::getegid()];
 
-    let group = libc::getgrgid(gid);
+            let count = libc::getgroups(0, ptr::null_mut());
 
-    let errno = errno();
+            if let Ok(len) = usize::try_from(count) {
+                let mut groups = vec![0; len];
 
-    if errno.0 != 0 {
-        return Err(Error::LibC(errno));
+                let count = libc::getgroups(count, groups.as_mut_ptr());
+                groups.truncate(usize::try_from(count).unwrap_or_default());
+                gids.extend(groups);
+            }
+
+            Self { uid, gids }
+        }
     }
 
-    if group.is_null() {
-        return Err(Error::InvalidGroup);
+    /// Looks up a user by name or, failing that, by numeric id. A numeric id that doesn't belong
+    /// to any user is treated as an account without any groups.
+    pub fn lookup(user: &str) -> Option<Self> {
+        let name = CString::new(user).ok()?;
+
+        let read = |pwd: &libc::passwd| unsafe {
+            let gids = group_list(CStr::from_ptr(pwd.pw_name), pwd.pw_gid);
+            Self {
+                uid: pwd.pw_uid,
+                gids,
+            }
+        };
+
+        let by_name = unsafe {
+            lookup_entry(
+                |pwd, buf, len, result| libc::getpwnam_r(name.as_ptr(), pwd, buf, len, result),
+                read,
+            )
+        };
+
+        if let Ok(Some(account)) = by_name {
+            return Some(account);
+        }
+
+        let uid = user.parse::<u32>().ok()?;
+
+        let by_id = unsafe {
+            lookup_entry(
+                |pwd, buf, len, result| libc::getpwuid_r(uid, pwd, buf, len, result),
+                read,
+            )
+        };
+
+        Some(by_id.ok().flatten().unwrap_or(Self { uid, gids: vec![] }))
     }
 
-    let libc::group { gr_name, .. } = *group;
+    /// Returns `true` if the account is exempt from permission checks.
+    pub const fn is_superuser(&self) -> bool {
+        self.uid == 0
+    }
+}
+
+/// Returns the ids of every group that the user `name` belongs to, including `gid` which is their
+/// primary group.
+unsafe fn group_list(name: &CStr, gid: libc::gid_t) -> Vec<u32> {
+    #[cfg(target_os = "macos")]
+    type Gid = libc::c_int;
+
+    #[cfg(not(target_os = "macos"))]
+    type Gid = libc::gid_t;
+
+    let mut len: libc::c_int = 32;
+
+    loop {
+        let mut groups: Vec<Gid> = vec![0; usize::try_from(len).unwrap_or_default()];
+        let capacity = len;
+
+        #[cfg(not(target_os = "macos"))]
+        let rc = libc::getgrouplist(name.as_ptr(), gid, groups.as_mut_ptr(), &mut len);
+
+        #[cfg(target_os = "macos")]
+        #[allow(clippy::cast_possible_wrap)]
+        let rc = libc::getgrouplist(name.as_ptr(), gid as Gid, groups.as_mut_ptr(), &mut len);
 
-    Ok(CStr::from_ptr(gr_name).to_string_lossy().to_string())
+        // The group list didn't fit in which case `len` now holds the number of groups.
+        if rc < 0 {
+            len = len.max(capacity * 2);
+            continue;
+        }
+
+        groups.truncate(usize::try_from(len).unwrap_or_default());
+
+        #[cfg(not(target_os = "macos"))]
+        return groups;
+
+        #[cfg(target_os = "macos")]
+        #[allow(clippy::cast_sign_loss)]
+        return groups.into_iter().map(|gid| gid as u32).collect();
+    }
+}
+
+/// Names of users or groups keyed by id, where ids without a user or group are `None`. Names are
+/// shared so that the many files belonging to the same owner don't each hold a copy.
+type Names = RwLock<HashMap<u32, Option<Arc<str>>>>;
+
+/// Names of users shared by every traversal thread so that each uid is only looked up once.
+static USER_NAMES: OnceLock<Names> = OnceLock::new();
+
+/// Names of groups shared by every traversal thread so that each gid is only looked up once.
+static GROUP_NAMES: OnceLock<Names> = OnceLock::new();
+
+/// Ids rendered as strings for when names aren't shown or don't exist.
+static NUMERIC_IDS: OnceLock<RwLock<HashMap<u32, Arc<str>>>> = OnceLock::new();
+
+/// Size of the buffer initially provided to reentrant lookups, which is grown as needed.
+const LOOKUP_BUF_SIZE: usize = 1024;
+
+/// Returns the name of the user associated with `uid` if there is one and it could be queried.
+pub fn user_name(uid: u32) -> Option<Arc<str>> {
+    try_user_name(uid).ok().flatten()
+}
+
+/// Returns the name of the group associated with `gid` if there is one and it could be queried.
+pub fn group_name(gid: u32) -> Option<Arc<str>> {
+    try_group_name(gid).ok().flatten()
+}
+
+/// Returns the name of the user associated with `uid`, `None` if there is no such user, or an
+/// error if it couldn't be determined whether the user exists.
+pub fn try_user_name(uid: u32) -> Result<Option<Arc<str>>, Error> {
+    cached_name(&USER_NAMES, uid, |uid| unsafe { try_get_user(uid) })
+}
+
+/// Returns the name of the group associated with `gid`, `None` if there is no such group, or an
+/// error if it couldn't be determined whether the group exists.
+pub fn try_group_name(gid: u32) -> Result<Option<Arc<str>>, Error> {
+    cached_name(&GROUP_NAMES, gid, |gid| unsafe { try_get_group(gid) })
+}
+
+/// Returns `id` as a string, shared with every other caller asking for the same id.
+pub fn numeric_id(id: u32) -> Arc<str> {
+    let cache = NUMERIC_IDS.get_or_init(RwLock::default);
+
+    if let Some(id) = cache.read().ok().and_then(|ids| ids.get(&id).cloned()) {
+        return id;
+    }
+
+    let rendered = Arc::<str>::from(id.to_string());
+
+    if let Ok(mut ids) = cache.write() {
+        ids.insert(id, Arc::clone(&rendered));
+    }
+
+    rendered
+}
+
+/// Returns the name associated with `id` from `cache`, querying it with `lookup` if it isn't
+/// cached yet, or `None` if `id` doesn't exist. Failures are only cached if the id doesn't exist
+/// as other failures, such as a directory service being unreachable, may be transient.
+fn cached_name<F>(cache: &OnceLock<Names>, id: u32, lookup: F) -> Result<Option<Arc<str>>, Error>
+where
+    F: FnOnce(u32) -> Result<String, Error>,
+{
+    let cache = cache.get_or_init(Names::default);
+
+    if let Some(name) = cache.read().ok().and_then(|names| names.get(&id).cloned()) {
+        return Ok(name);
+    }
+
+    let name = match lookup(id) {
+        Ok(name) => Some(Arc::from(name)),
+        Err(err @ Error::LibC(_)) => return Err(err),
+        Err(Error::InvalidUser | Error::InvalidGroup) => None,
+    };
+
+    if let Ok(mut names) = cache.write() {
+        names.insert(id, name.clone());
+    }
+
+    Ok(name)
+}
+
+/// Attempts to return the name of the group associated with `gid`.
+unsafe fn try_get_group(gid: libc::gid_t) -> Result<String, Error> {
+    let name = lookup_entry(
+        |grp, buf, len, result| libc::getgrgid_r(gid, grp, buf, len, result),
+        |grp: &libc::group| CStr::from_ptr(grp.gr_name).to_string_lossy().into_owned(),
+    )?;
+
+    name.ok_or(Error::InvalidGroup)
 }
 
 /// Attempts to return the name of the user associated with `uid`.
 unsafe fn try_get_user(uid: libc::uid_t) -> Result<String, Error> {
-    set_errno(Errno(0));
+    let name = lookup_entry(
+        |pwd, buf, len, result| libc::getpwuid_r(uid, pwd, buf, len, result),
+        |pwd: &libc::passwd| CStr::from_ptr(pwd.pw_name).to_string_lossy().into_owned(),
+    )?;
 
-    let pwd = libc::getpwuid(uid);
+    name.ok_or(Error::InvalidUser)
+}
 
-    let errno = errno();
+/// Calls `lookup`, one of the reentrant functions of the `getpwuid_r` family, with a buffer that
+/// is grown until the entry fits and then extracts what's needed from the entry with `read`.
+/// Returns `None` if there is no such entry.
+unsafe fn lookup_entry<T, R, L, F>(lookup: L, read: F) -> Result<Option<R>, Error>
+where
+    L: Fn(*mut T, *mut c_char, usize, *mut *mut T) -> c_int,
+    F: FnOnce(&T) -> R,
+{
+    let mut entry = MaybeUninit::<T>::uninit();
+    let mut buf = vec![0; LOOKUP_BUF_SIZE];
 
-    if errno.0 != 0 {
-        return Err(Error::LibC(errno));
-    }
+    loop {
+        let mut result = ptr::null_mut();
 
-    if pwd.is_null() {
-        return Err(Error::InvalidUser);
+        match lookup(entry.as_mut_ptr(), buf.as_mut_ptr(), buf.len(), &mut result) {
+            0 if result.is_null() => return Ok(None),
+            0 => return Ok(Some(read(&*result))),
+            libc::ERANGE => buf.resize(buf.len() * 2, 0),
+
+            // Some platforms report missing entries as errors.
+            libc::ENOENT | libc::ESRCH | libc::EBADF | libc::EPERM => return Ok(None),
+            errno => return Err(Error::LibC(Errno(errno))),
+        }
     }
+}
+
+#[test]
+fn test_cached_name() {
+    let cache = OnceLock::new();
+
+    let name = cached_name(&cache, 1000, |_| Ok(String::from("ferris"))).unwrap();
+    assert_eq!(name.as_deref(), Some("ferris"));
+
+    let name = cached_name(&cache, 1000, |_| panic!("Expected name to be cached")).unwrap();
+    assert_eq!(name.as_deref(), Some("ferris"));
 
-    let libc::passwd { pw_name, .. } = *pwd;
+    assert!(cached_name(&cache, 4242, |_| Err(Error::InvalidUser))
+        .unwrap()
+        .is_none());
+    assert!(cached_name(&cache, 4242, |_| panic!(
+        "Expected missing user to be cached"
+    ))
+    .unwrap()
+    .is_none());
 
-    Ok(CStr::from_ptr(pw_name).to_string_lossy().to_string())
+    // Failures that may be transient are reported as such and retried.
+    assert!(matches!(
+        cached_name(&cache, 7, |_| Err(Error::LibC(Errno(libc::EIO)))),
+        Err(Error::LibC(_))
+    ));
+    assert_eq!(
+        cached_name(&cache, 7, |_| Ok(String::from("lp")))
+            .unwrap()
+            .as_deref(),
+        Some("lp")
+    );
 }
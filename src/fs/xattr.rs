@@ -1,5 +1,24 @@
+use super::ug;
 use ignore::DirEntry;
-use std::{os::unix::ffi::OsStrExt, path::Path, ptr};
+use std::{
+    ffi::{CStr, CString},
+    fmt::{self, Display},
+    os::unix::ffi::OsStrExt,
+    path::Path,
+    ptr,
+};
+
+/// Name of the extended attribute that holds the POSIX access ACL on Linux.
+const ACL_ACCESS: &str = "system.posix_acl_access";
+
+/// Name of the extended attribute that holds the POSIX default ACL of directories on Linux.
+const ACL_DEFAULT: &str = "system.posix_acl_default";
+
+/// Name of the extended attribute that holds the security context of `SELinux`.
+const SELINUX: &str = "security.selinux";
+
+/// Name of the extended attribute that holds the Linux capabilities of executables.
+const CAPABILITY: &str = "security.capability";
 
 /// Allow extended attributes to be queried directly from the directory entry.
 impl ExtendedAttr for DirEntry {
@@ -17,6 +36,152 @@ pub trait ExtendedAttr {
     fn has_xattrs(&self) -> bool {
         unsafe { has_xattrs(self.path()) }
     }
+
+    /// Queries the filesystem to check if the implementor's path has Linux capabilities that are
+    /// granted when it's executed.
+    fn has_capabilities(&self) -> bool {
+        unsafe { get_xattr(self.path(), CAPABILITY) }.is_some_and(|caps| !caps.is_empty())
+    }
+
+    /// Queries the filesystem for the extended attributes of the implementor's path. POSIX ACLs
+    /// and `SELinux` contexts are always decoded whereas the values of all other attributes are
+    /// only retained, truncated to `value_limit` bytes, if a `value_limit` is provided.
+    fn xattrs(&self, value_limit: Option<usize>) -> Vec<Xattr> {
+        let path = self.path();
+
+        unsafe { list_xattrs(path) }
+            .into_iter()
+            .map(|name| {
+                let value = match name.as_str() {
+                    ACL_ACCESS | ACL_DEFAULT => {
+                        unsafe { get_xattr(path, &name) }.and_then(|raw| decode_acl(&raw))
+                    },
+                    SELINUX => unsafe { get_xattr(path, &name) }.map(|raw| {
+                        let context = raw.split(|b| *b == 0).next().unwrap_or_default();
+                        Value::Context(String::from_utf8_lossy(context).into_owned())
+                    }),
+                    _ => value_limit.and_then(|limit| {
+                        let mut bytes = unsafe { get_xattr(path, &name) }?;
+                        let len = bytes.len();
+                        bytes.truncate(limit);
+                        Some(Value::Raw { bytes, len })
+                    }),
+                };
+
+                Xattr { name, value }
+            })
+            .collect()
+    }
+}
+
+/// A single extended attribute of a file.
+#[derive(Debug)]
+pub struct Xattr {
+    pub name: String,
+
+    /// The value of the attribute if it was queried.
+    pub value: Option<Value>,
+}
+
+/// The value of an [Xattr].
+#[derive(Debug)]
+pub enum Value {
+    /// Entries of a POSIX ACL in the short text form of `getfacl` e.g. `user:alice:rw-`.
+    Acl(Vec<String>),
+
+    /// A security context of `SELinux` e.g. `unconfined_u:object_r:user_home_t:s0`.
+    Context(String),
+
+    /// The value as stored on disk, possibly truncated, along with the size of the whole value.
+    Raw { bytes: Vec<u8>, len: usize },
+}
+
+impl Display for Xattr {
+    /// ACLs and `SELinux` contexts are shown under the short labels `acl`, `default_acl`, and
+    /// `selinux`. Other values are shown as quoted strings if they're valid UTF-8 or in hex
+    /// otherwise, with truncated values ending in an ellipsis.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(ref value) = self.value else {
+            return write!(f, "{}", self.name);
+        };
+
+        match value {
+            Value::Acl(entries) => {
+                let label = if self.name == ACL_DEFAULT {
+                    "default_acl"
+                } else {
+                    "acl"
+                };
+                write!(f, "{label}={}", entries.join(","))
+            },
+            Value::Context(context) => write!(f, "selinux={context}"),
+            Value::Raw { bytes, len } => {
+                let ellipsis = if bytes.len() < *len { "\u{2026}" } else { "" };
+
+                if let Ok(text) = std::str::from_utf8(bytes) {
+                    return write!(f, "{}=\"{}{ellipsis}\"", self.name, text.escape_debug());
+                }
+
+                write!(f, "{}=0x", self.name)?;
+
+                for byte in bytes {
+                    write!(f, "{byte:02x}")?;
+                }
+
+                write!(f, "{ellipsis}")
+            },
+        }
+    }
+}
+
+/// Decodes the binary representation of a POSIX ACL as used by Linux which is a little-endian
+/// version number of `2` followed by 8-byte entries made up of a tag, permissions, and an id.
+fn decode_acl(raw: &[u8]) -> Option<Value> {
+    const USER_OBJ: u16 = 0x01;
+    const USER: u16 = 0x02;
+    const GROUP_OBJ: u16 = 0x04;
+    const GROUP: u16 = 0x08;
+    const MASK: u16 = 0x10;
+    const OTHER: u16 = 0x20;
+
+    let version = u32::from_le_bytes(raw.get(..4)?.try_into().ok()?);
+
+    if version != 2 {
+        return None;
+    }
+
+    raw[4..]
+        .chunks_exact(8)
+        .map(|entry| {
+            let tag = u16::from_le_bytes([entry[0], entry[1]]);
+            let perm = u16::from_le_bytes([entry[2], entry[3]]);
+            let id = u32::from_le_bytes([entry[4], entry[5], entry[6], entry[7]]);
+
+            let perms = [(4, 'r'), (2, 'w'), (1, 'x')]
+                .into_iter()
+                .map(|(bit, ch)| if perm & bit == 0 { '-' } else { ch })
+                .collect::<String>();
+
+            let entry = match tag {
+                USER_OBJ => format!("user::{perms}"),
+                USER => {
+                    let user = ug::user_name(id).unwrap_or_else(|| ug::numeric_id(id));
+                    format!("user:{user}:{perms}")
+                },
+                GROUP_OBJ => format!("group::{perms}"),
+                GROUP => {
+                    let group = ug::group_name(id).unwrap_or_else(|| ug::numeric_id(id));
+                    format!("group:{group}:{perms}")
+                },
+                MASK => format!("mask::{perms}"),
+                OTHER => format!("other::{perms}"),
+                _ => return None,
+            };
+
+            Some(entry)
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(Value::Acl)
 }
 
 /// Checks to see if a directory entry referred to by `path` has extended attributes. If the file
@@ -24,15 +189,122 @@ pub trait ExtendedAttr {
 unsafe fn has_xattrs(path: &Path) -> bool {
     use libc::{c_char, listxattr};
 
-    let path_ptr = {
-        let slice = path.as_os_str().as_bytes();
-        let slice_ptr = slice.as_ptr();
-        slice_ptr.cast::<c_char>()
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
     };
 
     #[cfg(not(target_os = "macos"))]
-    return 0 < listxattr(path_ptr, ptr::null_mut::<c_char>(), 0);
+    return 0 < listxattr(path.as_ptr(), ptr::null_mut::<c_char>(), 0);
 
     #[cfg(target_os = "macos")]
-    return 0 < listxattr(path_ptr, ptr::null_mut::<c_char>(), 0, 0);
+    return 0 < listxattr(path.as_ptr(), ptr::null_mut::<c_char>(), 0, 0);
+}
+
+/// Returns the names of the extended attributes of the file at `path`, following symlinks.
+unsafe fn list_xattrs(path: &Path) -> Vec<String> {
+    use libc::{c_char, listxattr};
+
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return vec![];
+    };
+
+    let list = |buf: *mut c_char, size: usize| {
+        #[cfg(not(target_os = "macos"))]
+        return listxattr(path.as_ptr(), buf, size);
+
+        #[cfg(target_os = "macos")]
+        return listxattr(path.as_ptr(), buf, size, 0);
+    };
+
+    let Ok(size) = usize::try_from(list(ptr::null_mut(), 0)) else {
+        return vec![];
+    };
+
+    let mut buf = vec![0_u8; size];
+
+    let Ok(size) = usize::try_from(list(buf.as_mut_ptr().cast(), buf.len())) else {
+        return vec![];
+    };
+
+    buf.truncate(size);
+
+    buf.split_inclusive(|b| *b == 0)
+        .filter_map(|name| CStr::from_bytes_with_nul(name).ok())
+        .map(|name| name.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Returns the value of the extended attribute `name` of the file at `path`, following symlinks.
+unsafe fn get_xattr(path: &Path, name: &str) -> Option<Vec<u8>> {
+    use libc::{c_void, getxattr};
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let name = CString::new(name).ok()?;
+
+    let get = |buf: *mut c_void, size: usize| {
+        #[cfg(not(target_os = "macos"))]
+        return getxattr(path.as_ptr(), name.as_ptr(), buf, size);
+
+        #[cfg(target_os = "macos")]
+        return getxattr(path.as_ptr(), name.as_ptr(), buf, size, 0, 0);
+    };
+
+    let size = usize::try_from(get(ptr::null_mut(), 0)).ok()?;
+
+    let mut buf = vec![0_u8; size];
+
+    let size = usize::try_from(get(buf.as_mut_ptr().cast(), buf.len())).ok()?;
+
+    buf.truncate(size);
+
+    Some(buf)
+}
+
+#[test]
+fn test_decode_acl() {
+    let mut raw = 2_u32.to_le_bytes().to_vec();
+
+    for (tag, perm, id) in [
+        (0x01_u16, 6_u16, u32::MAX),
+        (0x08, 5, 54321),
+        (0x20, 0, u32::MAX),
+    ] {
+        raw.extend(tag.to_le_bytes());
+        raw.extend(perm.to_le_bytes());
+        raw.extend(id.to_le_bytes());
+    }
+
+    let Some(Value::Acl(entries)) = decode_acl(&raw) else {
+        panic!("failed to decode ACL");
+    };
+
+    assert_eq!(entries, ["user::rw-", "group:54321:r-x", "other::---"]);
+    assert!(decode_acl(&raw[..3]).is_none());
+}
+
+#[test]
+fn test_display_xattr() {
+    let xattr = Xattr {
+        name: String::from("user.comment"),
+        value: Some(Value::Raw {
+            bytes: b"hello".to_vec(),
+            len: 11,
+        }),
+    };
+    assert_eq!(xattr.to_string(), "user.comment=\"hello\u{2026}\"");
+
+    let xattr = Xattr {
+        name: String::from("user.bin"),
+        value: Some(Value::Raw {
+            bytes: vec![0xff, 0x00],
+            len: 2,
+        }),
+    };
+    assert_eq!(xattr.to_string(), "user.bin=0xff00");
+
+    let xattr = Xattr {
+        name: String::from(SELINUX),
+        value: Some(Value::Context(String::from("system_u:object_r:tmp_t:s0"))),
+    };
+    assert_eq!(xattr.to_string(), "selinux=system_u:object_r:tmp_t:s0");
 }
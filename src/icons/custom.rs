@@ -0,0 +1,164 @@
+use super::Kind;
+use crate::styles::palette;
+use ansi_term::Style;
+use ignore::overrides::{Override, OverrideBuilder};
+use std::{ffi::OsStr, path::Path, sync::OnceLock};
+
+/// Runtime evaluated static that contains the user-defined icon mappings in the order that they
+/// were provided.
+static MAPPINGS: OnceLock<Vec<Mapping>> = OnceLock::new();
+
+/// A user-defined icon along with an optional style. Icons without a style take on the color of
+/// the file they belong to.
+#[derive(Clone, Debug)]
+pub struct Mapping {
+    matcher: Matcher,
+    icon: String,
+    style: Option<Style>,
+}
+
+/// What a [Mapping] applies to.
+#[derive(Clone, Debug)]
+enum Matcher {
+    /// Files whose extension is exactly this, without the leading `.`.
+    Ext(String),
+
+    /// Files whose name is exactly this.
+    Name(String),
+
+    /// Files whose name matches the glob.
+    Glob(Override),
+
+    /// Files of this kind.
+    Kind(Kind),
+}
+
+impl Mapping {
+    /// The user-defined icon.
+    pub fn icon(&self) -> &str {
+        &self.icon
+    }
+
+    /// The user-defined style of the icon, if any.
+    pub const fn style(&self) -> Option<Style> {
+        self.style
+    }
+}
+
+/// Initializes [`MAPPINGS`].
+pub fn init(mappings: &[Mapping]) {
+    MAPPINGS.set(mappings.to_vec()).unwrap();
+}
+
+/// Mappings that were most recently provided take precedence over earlier ones.
+fn find(predicate: impl Fn(&Matcher) -> bool) -> Option<&'static Mapping> {
+    MAPPINGS
+        .get()?
+        .iter()
+        .rev()
+        .find(|mapping| predicate(&mapping.matcher))
+}
+
+/// Attempts to find the user-defined icon for the exact file name.
+pub fn from_name(name: &OsStr) -> Option<&'static Mapping> {
+    find(|matcher| matches!(matcher, Matcher::Name(n) if OsStr::new(n) == name))
+}
+
+/// Attempts to find the user-defined icon whose glob matches the file name.
+pub fn from_glob(name: &OsStr, is_dir: bool) -> Option<&'static Mapping> {
+    find(|matcher| match matcher {
+        Matcher::Glob(ovr) => ovr.matched(Path::new(name), is_dir).is_whitelist(),
+        _ => false,
+    })
+}
+
+/// Attempts to find the user-defined icon for the file extension.
+pub fn from_ext(ext: &OsStr) -> Option<&'static Mapping> {
+    find(|matcher| matches!(matcher, Matcher::Ext(e) if OsStr::new(e) == ext))
+}
+
+/// Attempts to find the user-defined icon for the [Kind] of file.
+pub fn from_kind(kind: Kind) -> Option<&'static Mapping> {
+    find(|matcher| matches!(matcher, Matcher::Kind(k) if *k == kind))
+}
+
+/// Parses a [Mapping] of the form `KIND:PATTERN=ICON [STYLE]` where `KIND` is one of `ext`,
+/// `name`, `glob`, or `type`, e.g. `ext:rs=\u{e7a8} bold 208`. See [`palette::parse_style`] for
+/// the format of `STYLE`.
+pub fn parse_mapping(arg: &str) -> Result<Mapping, String> {
+    let invalid = || format!("expected KIND:PATTERN=ICON [STYLE] but found '{arg}'");
+
+    let (kind, rest) = arg.split_once(':').ok_or_else(invalid)?;
+    let (pattern, value) = rest.rsplit_once('=').ok_or_else(invalid)?;
+
+    if pattern.is_empty() {
+        return Err(invalid());
+    }
+
+    let matcher = match kind.trim() {
+        "ext" => Matcher::Ext(pattern.trim_start_matches('.').to_owned()),
+        "name" => Matcher::Name(pattern.to_owned()),
+        "glob" => {
+            let mut builder = OverrideBuilder::new("");
+            builder.add(pattern).map_err(|e| e.to_string())?;
+            Matcher::Glob(builder.build().map_err(|e| e.to_string())?)
+        },
+        "type" => Matcher::Kind(Kind::parse(pattern)?),
+        other => {
+            return Err(format!(
+                "unknown kind '{other}'; expected one of ext, name, glob, or type"
+            ))
+        },
+    };
+
+    let value = value.trim();
+
+    let (icon, style) = value
+        .split_once(char::is_whitespace)
+        .map_or((value, None), |(icon, spec)| (icon, Some(spec)));
+
+    if icon.is_empty() {
+        return Err(invalid());
+    }
+
+    let style = style.map(palette::parse_style).transpose()?;
+
+    Ok(Mapping {
+        matcher,
+        icon: icon.to_owned(),
+        style,
+    })
+}
+
+#[test]
+fn test_parse_mapping() -> Result<(), String> {
+    use ansi_term::Color;
+
+    let mapping = parse_mapping("ext:.rs=\u{e7a8} bold 208")?;
+    assert!(matches!(mapping.matcher, Matcher::Ext(ref e) if e == "rs"));
+    assert_eq!(mapping.icon(), "\u{e7a8}");
+    assert_eq!(mapping.style(), Some(Color::Fixed(208).bold()));
+
+    let mapping = parse_mapping("name:Justfile=\u{f0ad}")?;
+    assert!(matches!(mapping.matcher, Matcher::Name(ref n) if n == "Justfile"));
+    assert_eq!(mapping.style(), None);
+
+    let mapping = parse_mapping("glob:*.test.js=\u{f0668} yellow")?;
+    let Matcher::Glob(ref ovr) = mapping.matcher else {
+        return Err(String::from("expected a glob"));
+    };
+    assert!(ovr.matched("app.test.js", false).is_whitelist());
+    assert!(!ovr.matched("app.js", false).is_whitelist());
+
+    let mapping = parse_mapping("type:socket=S")?;
+    assert!(matches!(mapping.matcher, Matcher::Kind(Kind::Socket)));
+
+    assert!(parse_mapping("ext:rs").is_err());
+    assert!(parse_mapping("rs=x").is_err());
+    assert!(parse_mapping("path:rs=x").is_err());
+    assert!(parse_mapping("type:pipe=x").is_err());
+    assert!(parse_mapping("ext:rs= ").is_err());
+    assert!(parse_mapping("ext:rs=x blurple").is_err());
+
+    Ok(())
+}
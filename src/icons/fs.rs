@@ -1,95 +1,112 @@
+use super::{custom, Kind};
 use ansi_term::{ANSIGenericString, Style};
-use ignore::DirEntry;
-use std::{borrow::Cow, path::Path};
+use std::{borrow::Cow, ffi::OsStr, path::Path};
+
+/// How an icon ought to be colored.
+enum Paint {
+    /// The 8-bit color code that belongs to the icon.
+    Fixed(u8),
+
+    /// The color of the associated file which is based on `LS_COLORS`.
+    Inherit,
+
+    /// A user-defined style.
+    Custom(Style),
+}
 
 /// Computes a plain, colorless icon with given parameters.
 ///
 /// The precedent from highest to lowest in terms of which parameters determine the icon used
-/// is as followed: file-type, file-extension, and then file-name. If an icon cannot be
-/// computed the fall-back default icon is used.
+/// is as followed: user-defined file-name, glob, and file-type mappings, file-type,
+/// user-defined and then built-in file-extension, file-name, and then whether or not the file is
+/// executable. If an icon cannot be computed the fall-back default icon is used.
 ///
 /// If a directory entry is a link and the link target is provided, the link target will be
-/// used to determine the icon.
-pub fn compute(entry: &DirEntry, link_target: Option<&Path>) -> Cow<'static, str> {
-    let icon = entry
-        .file_type()
-        .and_then(super::icon_from_file_type)
-        .map(Cow::from);
-
-    if let Some(i) = icon {
-        return i;
-    }
-
-    let ext = match link_target {
-        Some(target) if entry.path_is_symlink() => target.extension(),
-        _ => entry.path().extension(),
-    };
-
-    let icon = ext
-        .and_then(super::icon_from_ext)
-        .map(|(_, i)| Cow::from(i));
-
-    if let Some(i) = icon {
-        return i;
-    }
-
-    let icon = super::icon_from_file_name(entry.file_name()).map(Cow::from);
-
-    if let Some(i) = icon {
-        return i;
-    }
-
-    Cow::from(super::get_default_icon().1)
+/// used to determine the icon. The [Kind] of the entry is expected to have been computed
+/// beforehand using [`Kind::of`].
+pub fn compute(name: &OsStr, kind: Option<Kind>, link_target: Option<&Path>) -> Cow<'static, str> {
+    let (icon, _paint) = resolve(name, kind, link_target);
+    Cow::from(icon)
 }
 
 /// Computes a plain, colored icon with given parameters. See [compute] for more details.
 pub fn compute_with_color(
-    entry: &DirEntry,
+    name: &OsStr,
+    kind: Option<Kind>,
     link_target: Option<&Path>,
     style: Option<Style>,
 ) -> Cow<'static, str> {
-    let icon = entry
-        .file_type()
-        .and_then(super::icon_from_file_type)
-        .map(Cow::from);
-
-    let paint_icon = |icon| match style {
-        Some(Style {
-            foreground: Some(fg),
-            ..
-        }) => {
-            let ansi_string: ANSIGenericString<str> = fg.bold().paint(icon);
-            let styled_icon = ansi_string.to_string();
-            Cow::from(styled_icon)
+    let (icon, paint) = resolve(name, kind, link_target);
+
+    match paint {
+        Paint::Fixed(code) => Cow::from(super::col(code, icon)),
+        Paint::Custom(custom_style) => Cow::from(custom_style.paint(icon).to_string()),
+        Paint::Inherit => match style {
+            Some(Style {
+                foreground: Some(fg),
+                ..
+            }) => {
+                let ansi_string: ANSIGenericString<str> = fg.bold().paint(icon);
+                Cow::from(ansi_string.to_string())
+            },
+            _ => Cow::from(icon),
         },
-        _ => icon,
+    }
+}
+
+/// Determines the icon and how it ought to be colored. See [compute] for the order of precedence.
+fn resolve(name: &OsStr, kind: Option<Kind>, link_target: Option<&Path>) -> (&'static str, Paint) {
+    // Executables are only distinguished when nothing more specific is known about the file.
+    let (special, executable) = match kind {
+        Some(Kind::Executable) => (None, true),
+        _ => (kind, false),
     };
 
-    if let Some(icon) = icon {
-        return paint_icon(icon);
+    let user_defined = custom::from_name(name)
+        .or_else(|| custom::from_glob(name, kind == Some(Kind::Dir)))
+        .or_else(|| special.and_then(custom::from_kind));
+
+    if let Some(mapping) = user_defined {
+        return from_mapping(mapping);
     }
 
-    let ext = match link_target {
-        Some(target) if entry.path_is_symlink() => target.extension(),
-        _ => entry.path().extension(),
-    };
+    if let Some(icon) = special.and_then(super::icon_from_kind) {
+        return (icon, Paint::Inherit);
+    }
+
+    let ext = link_target.map_or_else(|| Path::new(name).extension(), Path::extension);
+
+    if let Some(ext) = ext {
+        if let Some(mapping) = custom::from_ext(ext) {
+            return from_mapping(mapping);
+        }
 
-    let icon = ext
-        .and_then(super::icon_from_ext)
-        .map(|attrs| Cow::from(super::col(attrs.0, attrs.1)));
+        if let Some((code, icon)) = super::icon_from_ext(ext) {
+            return (icon, Paint::Fixed(code));
+        }
+    }
 
-    if let Some(i) = icon {
-        return i;
+    if let Some(icon) = super::icon_from_file_name(name) {
+        return (icon, Paint::Inherit);
     }
 
-    let icon = super::icon_from_file_name(entry.file_name())
-        .map(Cow::from)
-        .map(paint_icon);
+    if executable {
+        if let Some(mapping) = custom::from_kind(Kind::Executable) {
+            return from_mapping(mapping);
+        }
 
-    if let Some(i) = icon {
-        return i;
+        if let Some(icon) = super::icon_from_kind(Kind::Executable) {
+            return (icon, Paint::Inherit);
+        }
     }
 
     let (code, icon) = super::get_default_icon();
-    Cow::from(super::col(code, icon))
+    (icon, Paint::Fixed(code))
+}
+
+/// The icon of a user-defined [`custom::Mapping`]. Mappings without a style take on the color of
+/// the associated file.
+fn from_mapping(mapping: &'static custom::Mapping) -> (&'static str, Paint) {
+    let paint = mapping.style().map_or(Paint::Inherit, Paint::Custom);
+    (mapping.icon(), paint)
 }
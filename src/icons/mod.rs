@@ -1,12 +1,14 @@
-use crate::hash;
+use crate::{fs::kind::Kind, hash};
 use ansi_term::Color;
 use once_cell::sync::Lazy;
 use std::{
     collections::HashMap,
     ffi::{OsStr, OsString},
-    fs::FileType,
 };
 
+/// User-defined icon mappings.
+pub mod custom;
+
 /// Concerned with computing icons given filesystem parameters.
 pub mod fs;
 
@@ -16,15 +18,9 @@ fn icon_from_ext(ext: &OsStr) -> Option<(u8, &'static str)> {
     EXT_ICON_MAP.get(ext).copied()
 }
 
-/// Attempts to return an icon based on file type.
-fn icon_from_file_type(ft: FileType) -> Option<&'static str> {
-    if ft.is_dir() {
-        return FILE_TYPE_ICON_MAP.get("dir").copied();
-    } else if ft.is_symlink() {
-        return FILE_TYPE_ICON_MAP.get("symlink").copied();
-    }
-
-    None
+/// Attempts to return an icon based on the [Kind] of file.
+fn icon_from_kind(kind: Kind) -> Option<&'static str> {
+    FILE_TYPE_ICON_MAP.get(kind.name()).copied()
 }
 
 /// Attempts to get the icon associated with the special file kind.
@@ -52,8 +48,14 @@ static DEFAULT_ICON: Lazy<(u8, &str)> = Lazy::new(|| (66, "\u{f15b}"));
 /// Dev icons sourced from [`exa`](https://github.com/ogham/exa/blob/master/src/output/icons.rs)
 static FILE_TYPE_ICON_MAP: Lazy<HashMap<&str, &str>> = Lazy::new(|| {
     hash!(
-        "dir"     => "\u{f413}", // 
-        "symlink" => "\u{f482}"  // 
+        "dir"          => "\u{f413}", // 
+        "symlink"      => "\u{f482}", // 
+        "broken_link"  => "\u{f127}", // 
+        "socket"       => "\u{f1e6}", // 
+        "fifo"         => "\u{f0ec}", // 
+        "block_device" => "\u{f0a0}", // 
+        "char_device"  => "\u{f11c}", // 
+        "executable"   => "\u{f489}"  // 
     )
 });
 
@@ -21,7 +21,7 @@ use std::{
     io::{stdout, Write},
     process::ExitCode,
 };
-use tree::Tree;
+use tree::{cutoff, Tree};
 
 /// Operations to wrangle ANSI escaped strings.
 mod ansi;
@@ -58,26 +58,30 @@ mod tty;
 mod utils;
 
 fn main() -> ExitCode {
-    if let Err(e) = run() {
-        eprintln!("{e}");
-        return ExitCode::FAILURE;
+    match run() {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        },
     }
-
-    ExitCode::SUCCESS
 }
 
-fn run() -> Result<(), Box<dyn Error>> {
+fn run() -> Result<ExitCode, Box<dyn Error>> {
     let ctx = Context::try_init()?;
 
     if let Some(shell) = ctx.completions {
         clap_complete::generate(shell, &mut Context::command(), "erd", &mut stdout());
-        return Ok(());
+        return Ok(ExitCode::SUCCESS);
     }
 
-    styles::init(ctx.no_color());
+    styles::init(ctx.no_color(), ctx.theme, &ctx.style, ctx.branches);
+    icons::custom::init(&ctx.icon);
 
     let indicator = Indicator::maybe_init(&ctx);
 
+    cutoff::init(&ctx, indicator.as_ref().map(IndicatorHandle::mailbox));
+
     let (tree, ctx) = {
         match Tree::try_init(ctx, indicator.as_ref()) {
             Ok(res) => res,
@@ -88,6 +92,11 @@ fn run() -> Result<(), Box<dyn Error>> {
         }
     };
 
+    let exit_code = tree
+        .cutoff()
+        .and_then(cutoff::Cutoff::exit_status)
+        .map_or(ExitCode::SUCCESS, ExitCode::from);
+
     macro_rules! compute_output {
         ($t:ty) => {{
             let render = Engine::<$t>::new(tree, ctx);
@@ -127,5 +136,5 @@ fn run() -> Result<(), Box<dyn Error>> {
         let _ = writeln!(stdout(), "{output}");
     }
 
-    Ok(())
+    Ok(exit_code)
 }
@@ -1,4 +1,4 @@
-use crate::{context::Context, tty};
+use crate::context::Context;
 use crossterm::{
     cursor,
     terminal::{self, ClearType},
@@ -82,7 +82,7 @@ impl Default for Indicator<'_> {
 
 impl IndicatorHandle {
     /// The constructor for an [`IndicatorHandle`].
-    pub fn new(
+    pub const fn new(
         join_handle: Option<JoinHandle<Result<(), Error>>>,
         mailbox: SyncSender<Message>,
     ) -> Self {
@@ -123,26 +123,11 @@ impl IndicatorHandle {
     }
 }
 
-impl<'a> Indicator<'a> {
+impl Indicator<'_> {
     /// Initializes an [`Indicator`] returning an atomic reference counter of an [`IndicatorHandle`] if
-    /// a progress indicator is enabled via [`Context`]. Upon initialization an interrupt handler is
-    /// also registered. Sources of panic can come from [`IndicatorHandle::terminate`] or
-    /// [`ctrlc::set_handler`].
+    /// a progress indicator is enabled via [`Context`].
     pub fn maybe_init(ctx: &Context) -> Option<IndicatorHandle> {
-        (ctx.stdout_is_tty && !ctx.no_progress)
-            .then(Indicator::measure)
-            .map(|indicator| {
-                let mailbox = indicator.mailbox();
-
-                let int_handler = move || {
-                    let _ = mailbox.try_send(Message::Finish);
-                    tty::restore();
-                };
-
-                ctrlc::set_handler(int_handler).expect("Failed to set interrupt handler");
-
-                indicator
-            })
+        (ctx.stdout_is_tty && !ctx.no_progress).then(Indicator::measure)
     }
 
     /// Initializes a worker thread that owns [`Indicator`] that awaits on [`Message`]s to traverse
@@ -1,22 +1,22 @@
 use crate::{
-    context::Context,
+    context::{
+        column::{self, SizeWidths},
+        Context,
+    },
     disk_usage::{
-        file_size::{byte, DiskUsage, FileSize},
+        file_size::{byte, DiskUsage, FileSize, BINARY},
         units::{BinPrefix, PrefixKind, SiPrefix},
     },
     render::theme,
     styles,
     tree::node::Node,
 };
+use ansi_term::Style;
 use std::{
-    ffi::OsStr,
     fmt::{self, Display},
     path::Path,
 };
 
-#[cfg(unix)]
-use chrono::{DateTime, Local};
-
 #[cfg(unix)]
 use crate::{
     context::time,
@@ -38,7 +38,9 @@ pub enum Kind<'a> {
     FileName {
         prefix: Option<&'a str>,
     },
-    FilePath,
+    FilePath {
+        path: &'a Path,
+    },
     FileSize,
     #[cfg(unix)]
     Datetime,
@@ -52,6 +54,8 @@ pub enum Kind<'a> {
     Owner,
     #[cfg(unix)]
     Group,
+    #[cfg(unix)]
+    Blocks,
 }
 
 impl<'a> Cell<'a> {
@@ -71,14 +75,15 @@ impl<'a> Cell<'a> {
             Kind::FileName { prefix } => {
                 let pre = prefix.unwrap_or_default();
                 let name = theme::stylize_file_name(node);
+                let annotations = Self::annotations(node);
 
                 if !ctx.icons {
-                    return write!(f, "{pre}{name}");
+                    return write!(f, "{pre}{name}{annotations}");
                 }
 
                 let icon = node.compute_icon(ctx.no_color());
 
-                write!(f, "{pre}{icon} {name}")
+                write!(f, "{pre}{icon} {name}{annotations}")
             },
 
             _ => unreachable!(),
@@ -91,47 +96,155 @@ impl<'a> Cell<'a> {
         let node = self.node;
         let ctx = self.ctx;
 
-        let path = if node.depth() == 0 {
-            let file_name = node.file_name();
-            <OsStr as AsRef<Path>>::as_ref(file_name).display()
-        } else {
-            node.path()
-                .strip_prefix(ctx.dir_canonical())
-                .unwrap_or_else(|_| node.path())
-                .display()
+        let Kind::FilePath { path } = self.kind else {
+            unreachable!()
         };
 
+        let path = path.display();
+
         let formatted_path = node.style().map_or_else(
             || path.to_string(),
             |style| format!("{}", style.paint(path.to_string())),
         );
 
+        let annotations = Self::annotations(node);
+
         if !ctx.icons {
-            return write!(f, "{formatted_path}");
+            return write!(f, "{formatted_path}{annotations}");
         }
 
         let icon = node.compute_icon(ctx.no_color());
 
-        write!(f, "{icon} {formatted_path}")
+        write!(f, "{icon} {formatted_path}{annotations}")
+    }
+
+    /// The filesystem mounted on the [Node], whether it was read in full, its extended attributes,
+    /// and what `--audit` flagged about it, if any, each preceded by a space.
+    fn annotations(node: &Node) -> String {
+        let mut annotations = theme::stylize_mount_point(node)
+            .map(|mount| format!(" {mount}"))
+            .unwrap_or_default();
+
+        if let Some(incomplete) = theme::stylize_incomplete(node) {
+            annotations.push(' ');
+            annotations.push_str(&incomplete);
+        }
+
+        #[cfg(unix)]
+        if let Some(xattrs) = theme::stylize_xattrs(node) {
+            annotations.push(' ');
+            annotations.push_str(&xattrs);
+        }
+
+        #[cfg(unix)]
+        if let Some(findings) = theme::stylize_findings(node) {
+            annotations.push(' ');
+            annotations.push_str(&findings);
+        }
+
+        annotations
     }
 
-    /// Rules on how to render the file size.
+    /// Rules on how to render the file size. Each of the displayed disk usage metrics is rendered
+    /// in its own column followed by the physical to logical size ratio if requested.
     #[inline]
     fn fmt_file_size(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let node = self.node;
         let ctx = self.ctx;
 
-        let Some(file_size) = node.file_size() else {
-            return Self::fmt_size_placeholder(f, ctx)
+        if ctx.suppress_size {
+            return write!(f, "");
+        }
+
+        for (i, disk_usage) in ctx.disk_usages().iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            self.fmt_metric(f, *disk_usage)?;
+        }
+
+        if ctx.ratio {
+            write!(f, " ")?;
+            self.fmt_ratio(f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Rules on how to render the file size of a single disk usage metric.
+    #[inline]
+    fn fmt_metric(&self, f: &mut fmt::Formatter<'_>, disk_usage: DiskUsage) -> fmt::Result {
+        let ctx = self.ctx;
+        let widths = ctx.size_widths(disk_usage);
+
+        let Some(file_size) = self.node.file_size_of(disk_usage) else {
+            return Self::fmt_size_placeholder(f, disk_usage, widths)
         };
 
+        let gradient = Self::size_gradient_style(file_size, ctx);
+
         match file_size {
-            FileSize::Byte(metric) => Self::fmt_bytes(f, metric, ctx),
-            FileSize::Line(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx),
-            FileSize::Word(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx),
+            FileSize::Byte(metric) => Self::fmt_bytes(f, metric, widths, gradient, ctx),
+            FileSize::Line(metric) => {
+                Self::fmt_unitless_disk_usage(f, metric, widths, gradient, ctx)
+            },
+            FileSize::Word(metric) => {
+                Self::fmt_unitless_disk_usage(f, metric, widths, gradient, ctx)
+            },
+            FileSize::Char(metric) => {
+                Self::fmt_unitless_disk_usage(f, metric, widths, gradient, ctx)
+            },
+            FileSize::NonBlankLine(metric) => {
+                Self::fmt_unitless_disk_usage(f, metric, widths, gradient, ctx)
+            },
+            FileSize::Sloc(metric) => {
+                Self::fmt_unitless_disk_usage(f, metric, widths, gradient, ctx)
+            },
 
             #[cfg(unix)]
-            FileSize::Block(metric) => Self::fmt_block_usage(f, metric, ctx),
+            FileSize::Block(metric) => Self::fmt_block_usage(f, metric, widths, gradient, ctx),
+
+            FileSize::Binary(_) => Self::fmt_binary(f, widths),
+        }
+    }
+
+    /// The style along the size gradient for `file_size` if `--gradient` is enabled. Sizes are
+    /// placed on a logarithmic scale relative to the largest value of the same metric.
+    #[inline]
+    fn size_gradient_style(file_size: &FileSize, ctx: &Context) -> Option<&'static Style> {
+        if !ctx.gradient {
+            return None;
+        }
+
+        let max_value = ctx
+            .max_size_values
+            .get(&file_size.disk_usage())
+            .filter(|max_value| **max_value > 0)?;
+
+        let fraction = (file_size.value() as f64).ln_1p() / (*max_value as f64).ln_1p();
+
+        styles::get_gradient_style(fraction).ok()
+    }
+
+    /// Rules on how to render the physical to logical size ratio. Ratios below 100% indicate
+    /// sparse or filesystem-compressed files and are highlighted.
+    #[inline]
+    fn fmt_ratio(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max_width = self.ctx.max_ratio_width;
+
+        let Some(ratio) = self.node.size_ratio() else {
+            let formatted_placeholder = format!("{:>max_width$}", styles::PLACEHOLDER);
+
+            if let Ok(style) = styles::get_placeholder_style() {
+                return write!(f, "{}", style.paint(formatted_placeholder));
+            }
+            return write!(f, "{formatted_placeholder}");
+        };
+
+        let out = format!("{:>max_width$}", format!("{ratio}%"));
+
+        match styles::get_sparse_ratio_style() {
+            Ok(style) if ratio < 100 => write!(f, "{}", style.paint(out)),
+            _ => write!(f, "{out}"),
         }
     }
 
@@ -181,6 +294,29 @@ impl<'a> Cell<'a> {
         write!(f, "{formatted_ino}")
     }
 
+    /// Rules on how to format blocks for rendering.
+    #[cfg(unix)]
+    #[inline]
+    fn fmt_blocks(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let node = self.node;
+        let ctx = self.ctx;
+
+        let max_width = ctx.max_block_width;
+
+        let out = node.blocks().map_or_else(
+            || format!("{PLACEHOLDER:>max_width$}"),
+            |num| format!("{num:>max_width$}"),
+        );
+
+        let formatted_blocks = if let Ok(style) = styles::get_block_style() {
+            style.paint(out).to_string()
+        } else {
+            out
+        };
+
+        write!(f, "{formatted_blocks}")
+    }
+
     /// Rules on how to format owner.
     #[cfg(unix)]
     #[inline]
@@ -213,46 +349,50 @@ impl<'a> Cell<'a> {
         write!(f, "{group:>max_group_width$}")
     }
 
-    /// Rules on how to format datetime for rendering.
+    /// Rules on how to format datetime for rendering. Each of the selected timestamps is rendered
+    /// in its own column.
     #[cfg(unix)]
     #[inline]
     fn fmt_datetime(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let node = self.node;
+        for (i, stamp) in self.ctx.times().iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            self.fmt_stamp(f, *stamp)?;
+        }
+
+        Ok(())
+    }
+
+    /// Rules on how to format a single timestamp for rendering.
+    #[cfg(unix)]
+    #[inline]
+    fn fmt_stamp(&self, f: &mut fmt::Formatter<'_>, stamp: time::Stamp) -> fmt::Result {
         let ctx = self.ctx;
+        let time_format = ctx.time_format();
+        let width = time_format.width().unwrap_or(ctx.max_time_width);
 
-        let datetime = match ctx.time() {
-            time::Stamp::Create => node.created(),
-            time::Stamp::Access => node.accessed(),
-            time::Stamp::Mod => node.modified(),
-        };
+        let datetime = self.node.timestamp(stamp);
 
-        let out = datetime.map(DateTime::<Local>::from).map_or_else(
-            || format!("{PLACEHOLDER:>12}"),
-            |dt| format!("{:>12}", self.fmt_timestamp(dt)),
+        let out = datetime.map_or_else(
+            || format!("{PLACEHOLDER:>width$}"),
+            |time| format!("{:>width$}", time_format.format(time, ctx.time_zone())),
         );
 
-        let formatted_datetime = if let Ok(style) = styles::get_datetime_style() {
-            style.paint(out).to_string()
+        let gradient = if self.ctx.gradient {
+            datetime.and_then(|time| styles::get_gradient_style(time::age_fraction(time)).ok())
         } else {
-            out
+            None
         };
 
-        write!(f, "{formatted_datetime}")
-    }
-
-    /// Rules on how to format timestamp
-    #[cfg(unix)]
-    #[inline]
-    fn fmt_timestamp(&self, dt: DateTime<Local>) -> String {
-        let time_format = self.ctx.time_format();
-        let delayed_format = match time_format {
-            time::Format::Default => dt.format("%d %h %H:%M %g"),
-            time::Format::Iso => dt.format("%Y-%m-%d %H:%M:%S"),
-            time::Format::IsoStrict => dt.format("%Y-%m-%dT%H:%M:%S%Z"),
-            time::Format::Short => dt.format("%Y-%m-%d"),
-        };
+        let formatted_datetime =
+            if let Some(style) = gradient.or_else(|| styles::get_datetime_style().ok()) {
+                style.paint(out).to_string()
+            } else {
+                out
+            };
 
-        format!("{delayed_format:>12}")
+        write!(f, "{formatted_datetime}")
     }
 
     /// Rules on how to format permissions for rendering
@@ -273,22 +413,20 @@ impl<'a> Cell<'a> {
 
     /// Formatter for the placeholder for file sizes.
     #[inline]
-    fn fmt_size_placeholder(f: &mut fmt::Formatter<'_>, ctx: &Context) -> fmt::Result {
-        if ctx.suppress_size || ctx.max_size_width == 0 {
+    fn fmt_size_placeholder(
+        f: &mut fmt::Formatter<'_>,
+        disk_usage: DiskUsage,
+        widths: SizeWidths,
+    ) -> fmt::Result {
+        if widths.size == 0 {
             return write!(f, "");
         }
 
-        let mut padding = ctx.max_size_width + 1;
-
-        match ctx.disk_usage {
-            DiskUsage::Logical | DiskUsage::Physical => match ctx.unit {
-                PrefixKind::Si if ctx.human => padding += 2,
-                PrefixKind::Bin if ctx.human => padding += 3,
-                PrefixKind::Si => padding += 0,
-                PrefixKind::Bin => padding += 1,
-            },
-            _ => padding -= 1,
-        }
+        let padding = if disk_usage.is_byte_metric() {
+            widths.size + 1 + widths.unit
+        } else {
+            widths.size
+        };
 
         let formatted_placeholder = format!("{:>padding$}", styles::PLACEHOLDER);
 
@@ -299,11 +437,30 @@ impl<'a> Cell<'a> {
         }
     }
 
+    /// Formatter for text-based metrics of files that are binary.
+    #[inline]
+    fn fmt_binary(f: &mut fmt::Formatter<'_>, widths: SizeWidths) -> fmt::Result {
+        let max_size_width = widths.size;
+        let formatted_binary = format!("{BINARY:>max_size_width$}");
+
+        if let Ok(style) = styles::get_placeholder_style() {
+            write!(f, "{}", style.paint(formatted_binary))
+        } else {
+            write!(f, "{formatted_binary}")
+        }
+    }
+
     /// Rules to format disk usage as bytes
     #[inline]
-    fn fmt_bytes(f: &mut fmt::Formatter<'_>, metric: &byte::Metric, ctx: &Context) -> fmt::Result {
-        let max_size_width = ctx.max_size_width;
-        let max_unit_width = ctx.max_size_unit_width;
+    fn fmt_bytes(
+        f: &mut fmt::Formatter<'_>,
+        metric: &byte::Metric,
+        widths: SizeWidths,
+        gradient: Option<&Style>,
+        ctx: &Context,
+    ) -> fmt::Result {
+        let max_size_width = widths.size;
+        let max_unit_width = widths.unit;
         let out = format!("{metric}");
 
         let [size, unit]: [&str; 2] = out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
@@ -312,20 +469,22 @@ impl<'a> Cell<'a> {
             return write!(f, "{size:>max_size_width$} {unit:>max_unit_width$}");
         }
 
-        let color = if metric.human_readable {
-            styles::get_du_theme().unwrap().get(unit).unwrap()
-        } else {
-            match ctx.unit {
-                PrefixKind::Si => {
-                    let pre = SiPrefix::from(metric.value);
-                    styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
-                },
-                PrefixKind::Bin => {
-                    let pre = BinPrefix::from(metric.value);
-                    styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
-                },
+        let color = gradient.unwrap_or_else(|| {
+            if metric.human_readable {
+                styles::get_du_theme().unwrap().get(unit).unwrap()
+            } else {
+                match ctx.unit {
+                    PrefixKind::Si => {
+                        let pre = SiPrefix::from(metric.value);
+                        styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
+                    },
+                    PrefixKind::Bin => {
+                        let pre = BinPrefix::from(metric.value);
+                        styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
+                    },
+                }
             }
-        };
+        });
 
         let out = color.paint(format!("{size:>max_size_width$} {unit:>max_unit_width$}"));
 
@@ -337,14 +496,20 @@ impl<'a> Cell<'a> {
     fn fmt_block_usage(
         f: &mut fmt::Formatter<'_>,
         metric: &block::Metric,
+        widths: SizeWidths,
+        gradient: Option<&Style>,
         ctx: &Context,
     ) -> fmt::Result {
-        let max_size_width = ctx.max_size_width;
+        let max_size_width = widths.size;
 
         if ctx.no_color() {
             return write!(f, "{metric:>max_size_width$}");
         }
 
+        if let Some(style) = gradient {
+            return write!(f, "{}", style.paint(format!("{metric:>max_size_width$}")));
+        }
+
         let bytes = metric.value * u64::from(BLOCK_SIZE_BYTES);
 
         let color = match ctx.unit {
@@ -368,14 +533,16 @@ impl<'a> Cell<'a> {
     fn fmt_unitless_disk_usage<M: Display>(
         f: &mut fmt::Formatter<'_>,
         metric: &M,
+        widths: SizeWidths,
+        gradient: Option<&Style>,
         ctx: &Context,
     ) -> fmt::Result {
-        let max_size_width = ctx.max_size_width;
+        let max_size_width = widths.size;
 
         if ctx.no_color() {
             return write!(f, "{metric:>max_size_width$}");
         }
-        let color = styles::get_du_theme().unwrap().get("B").unwrap();
+        let color = gradient.unwrap_or_else(|| styles::get_du_theme().unwrap().get("B").unwrap());
 
         write!(f, "{}", color.paint(format!("{metric:>max_size_width$}")))
     }
@@ -385,7 +552,7 @@ impl Display for Cell<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.kind {
             Kind::FileName { prefix: _prefix } => self.fmt_name(f),
-            Kind::FilePath => self.fmt_path(f),
+            Kind::FilePath { .. } => self.fmt_path(f),
             Kind::FileSize => self.fmt_file_size(f),
 
             #[cfg(unix)]
@@ -405,6 +572,38 @@ impl Display for Cell<'_> {
 
             #[cfg(unix)]
             Kind::Group => self.fmt_group(f),
+
+            #[cfg(unix)]
+            Kind::Blocks => self.fmt_blocks(f),
+        }
+    }
+}
+
+impl From<column::Kind> for Kind<'_> {
+    fn from(column: column::Kind) -> Self {
+        match column {
+            column::Kind::Size => Self::FileSize,
+
+            #[cfg(unix)]
+            column::Kind::Perms => Self::Permissions,
+
+            #[cfg(unix)]
+            column::Kind::Owner => Self::Owner,
+
+            #[cfg(unix)]
+            column::Kind::Group => Self::Group,
+
+            #[cfg(unix)]
+            column::Kind::Ino => Self::Ino,
+
+            #[cfg(unix)]
+            column::Kind::Nlink => Self::Nlink,
+
+            #[cfg(unix)]
+            column::Kind::Blocks => Self::Blocks,
+
+            #[cfg(unix)]
+            column::Kind::Time => Self::Datetime,
         }
     }
 }
@@ -1,18 +1,17 @@
-use crate::{ansi::Escaped, tree::node::Node, Context};
+use crate::{ansi::Escaped, context::column, tree::node::Node, Context};
 use cell::Cell;
 use std::{
     fmt::{self, Display},
     marker::PhantomData,
+    path::Path,
 };
 
-#[cfg(unix)]
-use super::long;
-
 /// Concerned with rules to construct and a single cell in a given row.
 pub mod cell;
 
 pub struct Row<'a, T> {
     prefix: Option<&'a str>,
+    path: Option<&'a Path>,
     ctx: &'a Context,
     node: &'a Node,
     layout: PhantomData<T>,
@@ -24,10 +23,31 @@ pub struct Tree;
 /// For the [`super::Flat`] variant.
 pub struct Flat;
 
+/// The cells of the columns selected via [`Context::columns`] that precede the file name or path.
+struct Columns<'a> {
+    node: &'a Node,
+    ctx: &'a Context,
+    kinds: &'a [column::Kind],
+}
+
 impl<'a, T> Row<'a, T> {
-    pub const fn new(node: &'a Node, ctx: &'a Context, prefix: Option<&'a str>) -> Row<'a, T> {
+    pub const fn new(node: &'a Node, ctx: &'a Context, prefix: Option<&'a str>) -> Self {
         Self {
             prefix,
+            path: None,
+            node,
+            ctx,
+            layout: PhantomData,
+        }
+    }
+}
+
+impl<'a> Row<'a, Flat> {
+    /// Initializes a [Row] that displays `path` in place of the file name of `node`.
+    pub const fn with_path(node: &'a Node, ctx: &'a Context, path: &'a Path) -> Self {
+        Self {
+            prefix: None,
+            path: Some(path),
             node,
             ctx,
             layout: PhantomData,
@@ -35,13 +55,16 @@ impl<'a, T> Row<'a, T> {
     }
 }
 
-#[cfg(unix)]
 impl Display for Row<'_, Tree> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let node = self.node;
         let ctx = self.ctx;
 
-        let size = Cell::new(node, ctx, cell::Kind::FileSize);
+        let columns = Columns {
+            node,
+            ctx,
+            kinds: ctx.columns(),
+        };
         let name = Cell::new(
             node,
             ctx,
@@ -50,97 +73,75 @@ impl Display for Row<'_, Tree> {
             },
         );
 
-        let row = if ctx.long {
-            let optionals = long::Optionals::from(ctx);
-            let long_display = long::Display::new(optionals, node, ctx);
-
-            format!("{long_display} {size} {name}")
-        } else {
-            format!("{size} {name}")
-        };
+        let row = format!("{columns} {name}");
 
-        if ctx.truncate && ctx.window_width.is_some() {
-            let window_width = ctx.window_width.unwrap();
-            let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
-        } else {
-            write!(f, "{row}")
+        match ctx.window_width {
+            Some(window_width) if ctx.truncate => {
+                let out = <str as Escaped>::truncate(&row, window_width);
+                write!(f, "{out}")
+            },
+            _ => write!(f, "{row}"),
         }
     }
 }
 
-#[cfg(unix)]
 impl Display for Row<'_, Flat> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let node = self.node;
         let ctx = self.ctx;
 
-        let size = Cell::new(node, ctx, cell::Kind::FileSize);
-        let path = Cell::new(node, ctx, cell::Kind::FilePath);
-
-        let row = if ctx.long {
-            let optionals = long::Optionals::from(ctx);
-            let long_display = long::Display::new(optionals, node, ctx);
-
-            format!("{long_display}   {size} {path}")
-        } else {
-            format!("{size}   {path}")
-        };
-
-        if ctx.truncate && ctx.window_width.is_some() {
-            let window_width = ctx.window_width.unwrap();
-            let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
-        } else {
-            write!(f, "{row}")
-        }
-    }
-}
-
-#[cfg(not(unix))]
-impl Display for Row<'_, Tree> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let node = self.node;
-        let ctx = self.ctx;
-
-        let size = Cell::new(node, ctx, cell::Kind::FileSize);
-        let name = Cell::new(
+        let columns = Columns {
             node,
             ctx,
-            cell::Kind::FileName {
-                prefix: self.prefix,
+            kinds: ctx.columns(),
+        };
+        let path = self.path.unwrap_or_else(|| Path::new(node.file_name()));
+
+        let path = Cell::new(node, ctx, cell::Kind::FilePath { path });
+
+        // Unless columns are provided, disk usage is set apart from the long view rather than
+        // from the path.
+        let row = match ctx.columns().split_last() {
+            Some((&column::Kind::Size, long)) if !ctx.provides_columns() => {
+                let size = Cell::new(node, ctx, cell::Kind::FileSize);
+
+                if long.is_empty() {
+                    format!("{size}   {path}")
+                } else {
+                    let long = Columns {
+                        node,
+                        ctx,
+                        kinds: long,
+                    };
+                    format!("{long}   {size} {path}")
+                }
             },
-        );
-
-        let row = format!("{size} {name}");
+            _ => format!("{columns}   {path}"),
+        };
 
-        if ctx.truncate && ctx.window_width.is_some() {
-            let window_width = ctx.window_width.unwrap();
-            let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
-        } else {
-            write!(f, "{row}")
+        match ctx.window_width {
+            Some(window_width) if ctx.truncate => {
+                let out = <str as Escaped>::truncate(&row, window_width);
+                write!(f, "{out}")
+            },
+            _ => write!(f, "{row}"),
         }
     }
 }
 
-#[cfg(not(unix))]
-impl Display for Row<'_, Flat> {
+impl Display for Columns<'_> {
+    /// Renders each of the selected columns in order, separated by a single space.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let node = self.node;
-        let ctx = self.ctx;
+        for (i, &column) in self.kinds.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
 
-        let size = Cell::new(node, ctx, cell::Kind::FileSize);
-        let path = Cell::new(node, ctx, cell::Kind::FilePath);
+            let cell = Cell::new(self.node, self.ctx, cell::Kind::from(column));
 
-        let row = format!("{size}   {path}");
-
-        if ctx.truncate && ctx.window_width.is_some() {
-            let window_width = ctx.window_width.unwrap();
-            let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
-        } else {
-            write!(f, "{row}")
+            write!(f, "{cell}")?;
         }
+
+        Ok(())
     }
 }
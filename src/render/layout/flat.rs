@@ -3,7 +3,7 @@ use crate::{
         grid::{self, Row},
         Engine, Flat,
     },
-    tree::{count::FileCount, Tree},
+    tree::Tree,
 };
 use indextree::NodeEdge;
 use std::fmt::{self, Display};
@@ -30,15 +30,12 @@ impl Display for Engine<Flat> {
                 continue;
             }
 
-            let row = Row::<grid::Flat>::new(node, ctx, None);
+            let path = tree.relative_path(node_id);
+            let row = Row::<grid::Flat>::with_path(node, ctx, &path);
 
             writeln!(f, "{row}")?;
         }
 
-        if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
-        }
-
-        Ok(())
+        self.write_footer(f, file_count_data)
     }
 }
@@ -3,8 +3,7 @@ use crate::{
         grid::{self, Row},
         theme, Engine, Inverted,
     },
-    styles,
-    tree::{count::FileCount, Tree},
+    tree::Tree,
 };
 use std::fmt::{self, Display};
 
@@ -70,7 +69,7 @@ impl Display for Engine<Inverted> {
 
                 if next_depth == current_depth + 1 {
                     if last_sibling {
-                        base_prefix_components.push(styles::SEP);
+                        base_prefix_components.push(theme.get("sep").unwrap());
                     } else {
                         let prefix = theme.get("vt").unwrap();
                         base_prefix_components.push(prefix);
@@ -83,10 +82,6 @@ impl Display for Engine<Inverted> {
             }
         }
 
-        if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
-        }
-
-        Ok(())
+        self.write_footer(f, file_count_data)
     }
 }
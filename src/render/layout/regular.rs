@@ -3,8 +3,7 @@ use crate::{
         grid::{self, Row},
         theme, Engine, Regular,
     },
-    styles,
-    tree::{count::FileCount, Tree},
+    tree::Tree,
 };
 use indextree::NodeEdge;
 use std::fmt::{self, Display};
@@ -44,7 +43,7 @@ impl Display for Engine<Regular> {
                     let topmost_sibling = id.following_siblings(arena).nth(1).is_none();
 
                     if topmost_sibling {
-                        base_prefix_components.push(styles::SEP);
+                        base_prefix_components.push(theme.get("sep").unwrap());
                     } else {
                         base_prefix_components.push(theme.get("vt").unwrap());
                     }
@@ -94,10 +93,6 @@ impl Display for Engine<Regular> {
             }
         }
 
-        if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
-        }
-
-        Ok(())
+        self.write_footer(f, file_count_data)
     }
 }
@@ -1,5 +1,8 @@
-use crate::{context::Context, tree::Tree};
-use std::marker::PhantomData;
+use crate::{
+    context::Context,
+    tree::{count::FileCount, Tree},
+};
+use std::{fmt, marker::PhantomData};
 
 /// Module containing all of the layout variants.
 pub mod layout;
@@ -11,10 +14,6 @@ pub mod grid;
 /// output tree.
 pub mod theme;
 
-/// Concerned with how to construct the long output.
-#[cfg(unix)]
-pub mod long;
-
 /// The struct that is generic over T, which is generally expected to be a unit-struct that
 /// ultimately determines which variant to use for the output.
 pub struct Engine<T> {
@@ -55,4 +54,45 @@ impl<T> Engine<T> {
     const fn tree(&self) -> &Tree {
         &self.tree
     }
+
+    /// Writes what follows the rows of every layout: the total of `file_count_data`, which are
+    /// the file counts of the rows, followed by why traversal was cut short if it was and by
+    /// whichever summaries are enabled.
+    fn write_footer(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        mut file_count_data: Vec<FileCount>,
+    ) -> fmt::Result {
+        let ctx = self.context();
+        let tree = self.tree();
+
+        file_count_data.push(tree.folded().file_count);
+
+        if !file_count_data.is_empty() {
+            write!(f, "\n{}", FileCount::from(file_count_data))?;
+        }
+
+        if let Some(cutoff) = tree.cutoff() {
+            write!(f, "\n{cutoff}")?;
+        }
+
+        if ctx.sloc_summary {
+            let sloc_summary = tree.compute_sloc_summary();
+
+            if !sloc_summary.is_empty() {
+                write!(f, "\n\n{sloc_summary}")?;
+            }
+        }
+
+        if let Some(mount_summary) = tree.compute_mount_summary(ctx) {
+            write!(f, "\n\n{mount_summary}")?;
+        }
+
+        #[cfg(unix)]
+        if ctx.audit {
+            write!(f, "\n\nAudit: {}", tree.compute_audit_summary())?;
+        }
+
+        Ok(())
+    }
 }
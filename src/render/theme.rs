@@ -1,9 +1,10 @@
 use crate::{
+    fs::{link, mount::MountPoint},
     styles::{self, ThemesMap},
     tree::node::Node,
 };
 use ansi_term::{Color, Style};
-use std::borrow::Cow;
+use std::{borrow::Cow, ffi::OsStr};
 
 type Theme = Box<dyn FnMut(&Node) -> &'static ThemesMap>;
 
@@ -40,7 +41,7 @@ pub fn link_theme_getter() -> Theme {
 
 /// Stylizes the input `file_name` with the provided `style`. If `None` is provided then the
 /// underlying `String` is returned unmodified as a [Cow]. If the provided [Node] is a symlink then
-/// it will be styled accordingly.
+/// it will be styled accordingly, with broken symlinks being styled distinctly.
 pub fn stylize_file_name(node: &Node) -> Cow<'_, str> {
     let name = node.file_name();
     let style = node.style();
@@ -55,16 +56,129 @@ pub fn stylize_file_name(node: &Node) -> Cow<'_, str> {
         return name.to_string_lossy();
     };
 
+    let broken = node.is_broken_link()
+        || node
+            .link_chain()
+            .is_some_and(|chain| chain.end() != link::End::Resolved);
+
     if let Some(color) = style {
+        let (color, target_color) = match styles::get_broken_link_style() {
+            Ok(broken_style) if broken => (*broken_style, *broken_style),
+            _ => (color, Color::Red.normal()),
+        };
+
         let styled_name = color.paint(name.to_string_lossy());
-        let target_name = Color::Red.paint(format!("\u{2192} {}", target_name.to_string_lossy()));
+        let target_name = target_color.paint(link_target(node, target_name));
 
         return Cow::from(format!("{styled_name} {target_name}"));
     }
 
     let link = name.to_string_lossy();
-    let target = target_name.to_string_lossy();
-    Cow::from(format!("{link} \u{2192} {target}"))
+    let target = link_target(node, target_name);
+    Cow::from(format!("{link} {target}"))
+}
+
+/// Describes the filesystem mounted on the [Node] if it's a mount point e.g. `[ext4 /dev/sda1]`.
+/// Mount points that traversal didn't descend into are marked as such.
+pub fn stylize_mount_point(node: &Node) -> Option<String> {
+    let MountPoint { mount, crossed } = node.mount_point()?;
+
+    let annotation = if crossed {
+        format!("[{mount}]")
+    } else {
+        format!("[{mount}, not crossed]")
+    };
+
+    match styles::get_mount_style() {
+        Ok(style) => Some(style.paint(annotation).to_string()),
+        Err(_) => Some(annotation),
+    }
+}
+
+/// Marks directories that traversal was cut short of reading in full, whose sizes are therefore
+/// lower bounds.
+pub fn stylize_incomplete(node: &Node) -> Option<String> {
+    if !node.is_incomplete() {
+        return None;
+    }
+
+    let annotation = String::from("[incomplete]");
+
+    match styles::get_incomplete_style() {
+        Ok(style) => Some(style.paint(annotation).to_string()),
+        Err(_) => Some(annotation),
+    }
+}
+
+/// Lists the extended attributes of the [Node] in braces, if it has any that were queried.
+#[cfg(unix)]
+pub fn stylize_xattrs(node: &Node) -> Option<String> {
+    let xattrs = node.xattrs();
+
+    if xattrs.is_empty() {
+        return None;
+    }
+
+    let list = xattrs
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let annotation = format!("{{{list}}}");
+
+    match styles::get_xattrs_style() {
+        Ok(style) => Some(style.paint(annotation).to_string()),
+        Err(_) => Some(annotation),
+    }
+}
+
+/// Lists what `--audit` flagged about the [Node] in brackets, if anything.
+#[cfg(unix)]
+pub fn stylize_findings(node: &Node) -> Option<String> {
+    let findings = node.findings();
+
+    if findings.is_empty() {
+        return None;
+    }
+
+    let annotation = format!("[{findings}]");
+
+    match styles::get_audit_style() {
+        Ok(style) => Some(style.paint(annotation).to_string()),
+        Err(_) => Some(annotation),
+    }
+}
+
+/// Describes the target of a symlink. If the full resolution chain was computed then every hop is
+/// shown, with absolute targets marked by `\u{21D2}` rather than `\u{2192}`, followed by any
+/// notable properties of the chain in brackets.
+fn link_target(node: &Node, target_name: &OsStr) -> String {
+    let Some(chain) = node.link_chain() else {
+        return format!("\u{2192} {}", target_name.to_string_lossy());
+    };
+
+    let mut target = chain
+        .hops()
+        .iter()
+        .map(|hop| {
+            let arrow = if hop.is_absolute() {
+                '\u{21D2}'
+            } else {
+                '\u{2192}'
+            };
+            format!("{arrow} {}", hop.display())
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let flags = chain.flags();
+
+    if !flags.is_empty() {
+        target = format!("{target} [{}]", flags.join(", "));
+    }
+
+    target
 }
 
 /// Styles the symbolic notation of file permissions.
@@ -82,6 +196,13 @@ pub fn style_sym_permissions(node: &Node) -> String {
         format!("{perms} ")
     };
 
+    // Flagged files stand out by having their permissions painted as a whole.
+    if !node.findings().is_empty() {
+        if let Ok(style) = styles::get_audit_style() {
+            return style.paint(symb).to_string();
+        }
+    }
+
     if let Ok(theme) = styles::get_permissions_theme() {
         symb.chars()
             .filter_map(|ch| {
@@ -0,0 +1,106 @@
+use super::{DRT, SEP, UPRT, VT, VTRT};
+use clap::ValueEnum;
+
+/// Sets of characters used to draw the branches of the tree.
+#[derive(Clone, Copy, Debug, Default, ValueEnum, PartialEq, Eq)]
+pub enum Branches {
+    /// Thin box-drawing characters with sharp corners
+    #[default]
+    Sharp,
+
+    /// Thin box-drawing characters with rounded corners
+    Rounded,
+
+    /// Thick box-drawing characters
+    Heavy,
+
+    /// Plain ASCII characters for terminals and log files without Unicode support
+    Ascii,
+
+    /// Thin box-drawing characters with each level indented by two columns rather than three
+    Compact,
+}
+
+/// The individual pieces that branches are composed of. With the exception of `sep` which is
+/// always blank, every piece is drawn using the same number of columns.
+#[derive(Clone, Copy, Debug)]
+pub struct Glyphs {
+    /// Vertical line that connects siblings across the rows of their descendents.
+    pub vt: &'static str,
+
+    /// Corner that connects the top-most sibling in the regular layout.
+    pub drt: &'static str,
+
+    /// Corner that connects the bottom-most sibling in the inverted layout.
+    pub uprt: &'static str,
+
+    /// Junction that connects a sibling that has siblings on either side.
+    pub vtrt: &'static str,
+
+    /// Padding used in place of `vt` when there are no further siblings to connect.
+    pub sep: &'static str,
+}
+
+impl Branches {
+    /// The [Glyphs] that make up the branch style.
+    pub const fn glyphs(self) -> Glyphs {
+        match self {
+            Self::Sharp => Glyphs {
+                vt: VT,
+                drt: DRT,
+                uprt: UPRT,
+                vtrt: VTRT,
+                sep: SEP,
+            },
+            Self::Rounded => Glyphs {
+                vt: VT,
+                drt: "\u{256D}\u{2500} ",
+                uprt: "\u{2570}\u{2500} ",
+                vtrt: VTRT,
+                sep: SEP,
+            },
+            Self::Heavy => Glyphs {
+                vt: "\u{2503}  ",
+                drt: "\u{250F}\u{2501} ",
+                uprt: "\u{2517}\u{2501} ",
+                vtrt: "\u{2523}\u{2501} ",
+                sep: SEP,
+            },
+            Self::Ascii => Glyphs {
+                vt: "|  ",
+                drt: ",- ",
+                uprt: "`- ",
+                vtrt: "|- ",
+                sep: SEP,
+            },
+            Self::Compact => Glyphs {
+                vt: "\u{2502} ",
+                drt: "\u{250C} ",
+                uprt: "\u{2514} ",
+                vtrt: "\u{251C} ",
+                sep: "  ",
+            },
+        }
+    }
+}
+
+#[test]
+fn test_glyph_widths() {
+    for branches in Branches::value_variants() {
+        let Glyphs {
+            vt,
+            drt,
+            uprt,
+            vtrt,
+            sep,
+        } = branches.glyphs();
+
+        let width = sep.chars().count();
+
+        for glyph in [vt, drt, uprt, vtrt] {
+            assert_eq!(glyph.chars().count(), width, "{branches:?}: '{glyph}'");
+        }
+
+        assert!(sep.chars().all(char::is_whitespace));
+    }
+}
@@ -0,0 +1,103 @@
+use libc::{S_IFBLK, S_IFCHR, S_IFDIR, S_IFIFO, S_IFLNK, S_IFMT, S_IFREG, S_IFSOCK};
+use lscolors::{Indicator, LsColors, Style};
+use std::{env, path::Path, sync::OnceLock};
+
+/// Styles that stand in for that of a fallback indicator while probing `LS_COLORS`. There are two
+/// of them as the style of the probed indicator could happen to be the same as one of them.
+const PROBES: [&str; 2] = ["38;5;1;48;5;2", "38;5;3;48;5;4"];
+
+/// Which of the indicators that depend on a file's permissions, its number of hard links, or on
+/// whether the target of a symlink exists have a style of their own. Like `ls`, files are only
+/// told apart from others of the same type if they do; an executable is otherwise styled as a
+/// regular file, file extension and all.
+struct Assigned {
+    setuid: bool,
+    setgid: bool,
+    executable: bool,
+    multiple_hard_links: bool,
+    sticky_other_writable: bool,
+    other_writable: bool,
+    sticky: bool,
+    orphaned: bool,
+}
+
+/// A runtime evaluated static. See [`Assigned`].
+static ASSIGNED: OnceLock<Assigned> = OnceLock::new();
+
+impl Assigned {
+    /// [`LsColors`] doesn't expose whether an indicator has a style of its own or falls back to
+    /// that of a more general indicator, so `LS_COLORS` is parsed again with the style of the
+    /// fallback replaced: the indicator has a style of its own if it's unaffected.
+    fn probe() -> Self {
+        let ls_colors = env::var("LS_COLORS").unwrap_or_default();
+
+        let assigned = |indicator: Indicator, fallback: &str| {
+            PROBES.into_iter().any(|probe| {
+                let probed = LsColors::from_string(&format!("{ls_colors}:{fallback}={probe}"));
+                probed.style_for_indicator(indicator) != Style::from_ansi_sequence(probe).as_ref()
+            })
+        };
+
+        Self {
+            setuid: assigned(Indicator::Setuid, "fi"),
+            setgid: assigned(Indicator::Setgid, "fi"),
+            executable: assigned(Indicator::ExecutableFile, "fi"),
+            multiple_hard_links: assigned(Indicator::MultipleHardLinks, "fi"),
+            sticky_other_writable: assigned(Indicator::StickyAndOtherWritable, "di"),
+            other_writable: assigned(Indicator::OtherWritable, "di"),
+            sticky: assigned(Indicator::Sticky, "di"),
+            orphaned: assigned(Indicator::OrphanedSymbolicLink, "ln"),
+        }
+    }
+}
+
+/// The style of the file at `path` given its raw `st_mode` and number of hard links as opposed to
+/// its [`std::fs::Metadata`], with the same outcome as
+/// [`LsColors::style_for_path_with_metadata`]. `broken` is whether the file is a symlink whose
+/// target doesn't exist.
+pub fn style_for_mode<'a>(
+    ls_colors: &'a LsColors,
+    path: &Path,
+    mode: u32,
+    nlink: u64,
+    broken: bool,
+) -> Option<&'a Style> {
+    let assigned = ASSIGNED.get_or_init(Assigned::probe);
+
+    let indicator = match mode & S_IFMT {
+        S_IFREG => {
+            if assigned.setuid && mode & 0o4000 != 0 {
+                Indicator::Setuid
+            } else if assigned.setgid && mode & 0o2000 != 0 {
+                Indicator::Setgid
+            } else if assigned.executable && mode & 0o111 != 0 {
+                Indicator::ExecutableFile
+            } else if assigned.multiple_hard_links && nlink > 1 {
+                Indicator::MultipleHardLinks
+            } else {
+                // Regular files are styled by their file extension if there's a style for it.
+                return ls_colors.style_for_path_with_metadata(path, None);
+            }
+        },
+        S_IFDIR => {
+            if assigned.sticky_other_writable && mode & 0o1002 == 0o1002 {
+                Indicator::StickyAndOtherWritable
+            } else if assigned.other_writable && mode & 0o002 != 0 {
+                Indicator::OtherWritable
+            } else if assigned.sticky && mode & 0o1000 != 0 {
+                Indicator::Sticky
+            } else {
+                Indicator::Directory
+            }
+        },
+        S_IFLNK if assigned.orphaned && broken => Indicator::OrphanedSymbolicLink,
+        S_IFLNK => Indicator::SymbolicLink,
+        S_IFIFO => Indicator::FIFO,
+        S_IFSOCK => Indicator::Socket,
+        S_IFBLK => Indicator::BlockDevice,
+        S_IFCHR => Indicator::CharacterDevice,
+        _ => Indicator::MissingFile,
+    };
+
+    ls_colors.style_for_indicator(indicator)
+}
@@ -1,12 +1,24 @@
 use crate::hash;
 use ansi_term::{Color, Style};
+use branches::{Branches, Glyphs};
 use error::Error;
 use lscolors::LsColors;
+use palette::{Override, Palette, Preset};
 use std::{collections::HashMap, sync::OnceLock};
 
+/// Selectable sets of characters used to draw the branches of the tree.
+pub mod branches;
+
 /// Errors for this module.
 pub mod error;
 
+/// Styling files by their raw mode rather than by their [`std::fs::Metadata`].
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub mod indicator;
+
+/// Built-in color themes and user-defined overrides of individual styles.
+pub mod palette;
+
 /// Used as general placeholder for an empty field.
 pub const PLACEHOLDER: &str = "-";
 
@@ -53,6 +65,31 @@ static PERMISSIONS_THEME: OnceLock<HashMap<char, Style>> = OnceLock::new();
 #[cfg(unix)]
 static OCTAL_PERMISSIONS_STYLE: OnceLock<Style> = OnceLock::new();
 
+/// Runtime evaluated static that contains style for physical to logical size ratios of sparse or
+/// compressed files.
+static SPARSE_RATIO_STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Runtime evaluated static that contains the gradient of styles used to color timestamps by age
+/// and disk usage by size, ordered from recent or small to old or large.
+static GRADIENT: OnceLock<Vec<Style>> = OnceLock::new();
+
+/// Runtime evaluated static that contains style for the names and targets of broken symlinks.
+static BROKEN_LINK_STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Runtime evaluated static that contains style for the filesystem type and source of mount points.
+static MOUNT_STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Runtime evaluated static that contains style for directories that weren't read in full.
+static INCOMPLETE_STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Runtime evaluated static that contains style for extended attributes listed after file names.
+#[cfg(unix)]
+static XATTRS_STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Runtime evaluated static that contains style for findings of `--audit`.
+#[cfg(unix)]
+static AUDIT_STYLE: OnceLock<Style> = OnceLock::new();
+
 /// Runtime evaluated static that contains style for the general use placeholder "-".
 static PLACEHOLDER_STYLE: OnceLock<Style> = OnceLock::new();
 
@@ -72,6 +109,10 @@ static GROUP_STYLE: OnceLock<Style> = OnceLock::new();
 #[cfg(unix)]
 static NLINK_STYLE: OnceLock<Style> = OnceLock::new();
 
+/// Runtime evaluated static that contains style for the number of allocated blocks.
+#[cfg(unix)]
+static BLOCK_STYLE: OnceLock<Style> = OnceLock::new();
+
 /// Runtime evaluated static that contains style for datetime column.
 #[cfg(unix)]
 static DATETIME_STYLE: OnceLock<Style> = OnceLock::new();
@@ -80,16 +121,23 @@ static DATETIME_STYLE: OnceLock<Style> = OnceLock::new();
 pub type ThemesMap = HashMap<&'static str, String>;
 
 /// Initializes both [`LS_COLORS`] and all themes. If `plain` argument is `true` then plain colorless
-/// themes are used and [`LS_COLORS`] won't be initialized.
-pub fn init(plain: bool) {
+/// themes are used and [`LS_COLORS`] won't be initialized. Otherwise themes are derived from the
+/// `preset` with the user-defined `overrides` applied on top. Tree branches are drawn using the
+/// glyphs of `branches` either way.
+pub fn init(plain: bool, preset: Preset, overrides: &[Override], branches: Branches) {
     #[cfg(windows)]
     let _ = ansi_term::enable_ansi_support();
 
+    let glyphs = branches.glyphs();
+
     if plain {
-        init_plain();
+        init_plain(&glyphs);
     } else {
+        let mut palette = Palette::preset(preset);
+        palette.apply(overrides);
+
         init_ls_colors();
-        init_themes();
+        init_themes(&palette, &glyphs);
     }
 }
 
@@ -143,6 +191,64 @@ pub fn get_placeholder_style() -> Result<&'static Style, Error<'static>> {
         .ok_or(Error::Uninitialized("PLACEHOLDER_STYLE"))
 }
 
+/// Getter for the style at `fraction` of the way along [`GRADIENT`] where `0.0` is the recent or
+/// small end and `1.0` is the old or large end. Returns an error if not initialized.
+#[inline]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn get_gradient_style(fraction: f64) -> Result<&'static Style, Error<'static>> {
+    let gradient = GRADIENT.get().ok_or(Error::Uninitialized("GRADIENT"))?;
+    let max_index = gradient.len().saturating_sub(1);
+    let index = (fraction.clamp(0.0, 1.0) * max_index as f64).round() as usize;
+
+    gradient.get(index).ok_or(Error::Uninitialized("GRADIENT"))
+}
+
+/// Getter for [`BROKEN_LINK_STYLE`]. Returns an error if not initialized.
+#[inline]
+pub fn get_broken_link_style() -> Result<&'static Style, Error<'static>> {
+    BROKEN_LINK_STYLE
+        .get()
+        .ok_or(Error::Uninitialized("BROKEN_LINK_STYLE"))
+}
+
+/// Getter for [`MOUNT_STYLE`]. Returns an error if not initialized.
+#[inline]
+pub fn get_mount_style() -> Result<&'static Style, Error<'static>> {
+    MOUNT_STYLE.get().ok_or(Error::Uninitialized("MOUNT_STYLE"))
+}
+
+/// Getter for [`INCOMPLETE_STYLE`]. Returns an error if not initialized.
+#[inline]
+pub fn get_incomplete_style() -> Result<&'static Style, Error<'static>> {
+    INCOMPLETE_STYLE
+        .get()
+        .ok_or(Error::Uninitialized("INCOMPLETE_STYLE"))
+}
+
+/// Getter for [`XATTRS_STYLE`]. Returns an error if not initialized.
+#[cfg(unix)]
+#[inline]
+pub fn get_xattrs_style() -> Result<&'static Style, Error<'static>> {
+    XATTRS_STYLE
+        .get()
+        .ok_or(Error::Uninitialized("XATTRS_STYLE"))
+}
+
+/// Getter for [`AUDIT_STYLE`]. Returns an error if not initialized.
+#[cfg(unix)]
+#[inline]
+pub fn get_audit_style() -> Result<&'static Style, Error<'static>> {
+    AUDIT_STYLE.get().ok_or(Error::Uninitialized("AUDIT_STYLE"))
+}
+
+/// Getter for [`SPARSE_RATIO_STYLE`]. Returns an error if not initialized.
+#[inline]
+pub fn get_sparse_ratio_style() -> Result<&'static Style, Error<'static>> {
+    SPARSE_RATIO_STYLE
+        .get()
+        .ok_or(Error::Uninitialized("SPARSE_RATIO_STYLE"))
+}
+
 /// Getter for [`INO_STYLE`]. Returns an error if not initialized.
 #[cfg(unix)]
 #[inline]
@@ -150,6 +256,13 @@ pub fn get_ino_style() -> Result<&'static Style, Error<'static>> {
     INO_STYLE.get().ok_or(Error::Uninitialized("INO_STYLE"))
 }
 
+/// Getter for [`BLOCK_STYLE`]. Returns an error if not initialized.
+#[cfg(unix)]
+#[inline]
+pub fn get_block_style() -> Result<&'static Style, Error<'static>> {
+    BLOCK_STYLE.get().ok_or(Error::Uninitialized("BLOCK_STYLE"))
+}
+
 /// Getter for [`OWNER_STYLE`]. Returns an error if not initialized.
 #[cfg(unix)]
 #[inline]
@@ -189,91 +302,101 @@ fn init_ls_colors() {
 }
 
 /// Colorless themes
-fn init_plain() {
-    let theme = hash! {
-        "vt" => VT.to_owned(),
-        "uprt" => UPRT.to_owned(),
-        "drt" => DRT.to_owned(),
-        "vtrt" => VTRT.to_owned()
-    };
-    TREE_THEME.set(theme).unwrap();
+fn init_plain(glyphs: &Glyphs) {
+    let plain = Style::new();
 
-    let link_theme = hash! {
-        "vt" => VT.to_owned(),
-        "uprt" => UPRT.to_owned(),
-        "drt" => DRT.to_owned(),
-        "vtrt" => VTRT.to_owned()
+    TREE_THEME.set(branch_theme(glyphs, plain)).unwrap();
+    LINK_THEME.set(branch_theme(glyphs, plain)).unwrap();
+}
+
+/// Paints each of the `glyphs` with `style`. Plain styles leave the glyphs untouched. The blank
+/// separator is never painted.
+fn branch_theme(glyphs: &Glyphs, style: Style) -> ThemesMap {
+    let paint = |glyph: &str| {
+        if style.is_plain() {
+            glyph.to_owned()
+        } else {
+            format!("{}", style.paint(glyph))
+        }
     };
-    LINK_THEME.set(link_theme).unwrap();
+
+    hash! {
+        "vt" => paint(glyphs.vt),
+        "uprt" => paint(glyphs.uprt),
+        "drt" => paint(glyphs.drt),
+        "vtrt" => paint(glyphs.vtrt),
+        "sep" => glyphs.sep.to_owned()
+    }
 }
 
 /// Initialize themes for the `--long` view.
 #[cfg(unix)]
 #[inline]
-fn init_themes_for_long_view() {
+fn init_themes_for_long_view(palette: &Palette) {
+    let perms = &palette.permissions;
+
     let permissions_theme = hash! {
-        '-' | '.' => Color::Purple.normal(),
-        'd' => Color::Blue.bold(),
-        'l' => Color::Red.bold(),
-        'r' => Color::Green.bold(),
-        'w' => Color::Yellow.bold(),
-        'x' | 's' | 'S' | 't' | 'T' => Color::Red.bold(),
-        '@' => Color::Cyan.bold(),
+        '-' | '.' => perms.none,
+        'd' => perms.dir,
+        'l' => perms.link,
+        'r' => perms.read,
+        'w' => perms.write,
+        'x' | 's' | 'S' | 't' | 'T' => perms.exec,
+        '@' => perms.xattr,
         ' ' => Color::White.normal()
     };
     PERMISSIONS_THEME.set(permissions_theme).unwrap();
 
-    let octal_permissions_style = Color::Purple.bold();
     OCTAL_PERMISSIONS_STYLE
-        .set(octal_permissions_style)
+        .set(palette.octal_permissions)
         .unwrap();
 
-    let ino_style = Color::Cyan.bold();
-    INO_STYLE.set(ino_style).unwrap();
+    INO_STYLE.set(palette.ino).unwrap();
 
-    let nlink_style = Color::Red.bold();
-    NLINK_STYLE.set(nlink_style).unwrap();
+    NLINK_STYLE.set(palette.nlink).unwrap();
 
-    let datetime_style = Color::Purple.bold();
-    DATETIME_STYLE.set(datetime_style).unwrap();
+    BLOCK_STYLE.set(palette.blocks).unwrap();
 
-    let owner_style = Color::Cyan.bold();
-    OWNER_STYLE.set(owner_style).unwrap();
+    DATETIME_STYLE.set(palette.datetime).unwrap();
 
-    let group_style = Color::Green.bold();
-    GROUP_STYLE.set(group_style).unwrap();
-}
+    OWNER_STYLE.set(palette.owner).unwrap();
 
-/// Initializes all color themes.
-fn init_themes() {
-    let theme = hash! {
-        "vt" => format!("{}", Color::Purple.paint(VT)),
-        "uprt" => format!("{}", Color::Purple.paint(UPRT)),
-        "drt" => format!("{}", Color::Purple.paint(DRT)),
-        "vtrt" => format!("{}", Color::Purple.paint(VTRT))
-    };
-    TREE_THEME.set(theme).unwrap();
+    GROUP_STYLE.set(palette.group).unwrap();
 
-    let link_theme = hash! {
-        "vt" => format!("{}", Color::Red.paint(VT)),
-        "uprt" => format!("{}", Color::Red.paint(UPRT)),
-        "drt" => format!("{}", Color::Red.paint(DRT)),
-        "vtrt" => format!("{}", Color::Red.paint(VTRT))
-    };
-    LINK_THEME.set(link_theme).unwrap();
-
-    let du_theme = hash! {
-        "B" => Color::Cyan.bold(),
-        "KB" | "KiB" => Color::Yellow.bold(),
-        "MB" | "MiB" => Color::Green.bold(),
-        "GB" | "GiB" => Color::Red.bold(),
-        "TB" | "TiB" => Color::Blue.bold()
-    };
+    XATTRS_STYLE.set(palette.xattrs).unwrap();
+
+    AUDIT_STYLE.set(palette.audit).unwrap();
+}
+
+/// Initializes all color themes from the provided [Palette] and [Glyphs].
+fn init_themes(palette: &Palette, glyphs: &Glyphs) {
+    TREE_THEME.set(branch_theme(glyphs, palette.tree)).unwrap();
+    LINK_THEME.set(branch_theme(glyphs, palette.link)).unwrap();
+
+    let du_theme = [
+        "B", "KB", "KiB", "MB", "MiB", "GB", "GiB", "TB", "TiB", "PB", "PiB",
+    ]
+    .into_iter()
+    .map(|unit| (unit, palette.size(unit)))
+    .collect();
     DU_THEME.set(du_theme).unwrap();
 
-    let placeholder_style = Color::Purple.normal();
-    PLACEHOLDER_STYLE.set(placeholder_style).unwrap();
+    PLACEHOLDER_STYLE.set(palette.placeholder).unwrap();
+
+    BROKEN_LINK_STYLE.set(palette.broken_link).unwrap();
+
+    MOUNT_STYLE.set(palette.mount).unwrap();
+
+    INCOMPLETE_STYLE.set(palette.incomplete).unwrap();
+
+    SPARSE_RATIO_STYLE.set(palette.sparse_ratio).unwrap();
+
+    let gradient = [46, 82, 118, 154, 190, 226, 220, 214, 208, 202, 196]
+        .into_iter()
+        .map(|code| Color::Fixed(code).normal())
+        .collect();
+    GRADIENT.set(gradient).unwrap();
 
     #[cfg(unix)]
-    init_themes_for_long_view();
+    init_themes_for_long_view(palette);
 }
@@ -0,0 +1,513 @@
+use ansi_term::{Color, Style};
+use clap::ValueEnum;
+
+/// Built-in color themes that serve as the starting point for any user-defined overrides.
+#[derive(Clone, Copy, Debug, Default, ValueEnum, PartialEq, Eq)]
+pub enum Preset {
+    /// The classic erdtree theme made up of the basic ANSI colors
+    #[default]
+    Default,
+
+    /// Cool and muted tones of the Nord color scheme
+    Nord,
+
+    /// Vivid purples, pinks, and greens of the Dracula color scheme
+    Dracula,
+
+    /// Precise tones of the Solarized color scheme
+    Solarized,
+
+    /// No hues; emphasis by way of bold and dimmed text only
+    Monochrome,
+}
+
+/// Every style that can be configured by the user.
+#[cfg_attr(windows, allow(dead_code))]
+#[derive(Clone, Copy, Debug)]
+pub struct Palette {
+    pub tree: Style,
+    pub link: Style,
+
+    /// Names and targets of symlinks whose target doesn't exist.
+    pub broken_link: Style,
+
+    /// Filesystem type and source of mount points.
+    pub mount: Style,
+
+    /// Extended attributes listed after file names.
+    pub xattrs: Style,
+
+    /// Findings of `--audit` and the permissions of the files that they apply to.
+    pub audit: Style,
+
+    /// Directories that traversal was cut short of reading in full.
+    pub incomplete: Style,
+
+    pub placeholder: Style,
+    pub sparse_ratio: Style,
+    pub octal_permissions: Style,
+    pub ino: Style,
+    pub owner: Style,
+    pub group: Style,
+    pub nlink: Style,
+    pub blocks: Style,
+    pub datetime: Style,
+
+    /// Styles of the size units from bytes up to petabytes. Binary and SI prefixes of the same
+    /// magnitude share a style.
+    pub sizes: [Style; 6],
+
+    pub permissions: Permissions,
+}
+
+/// Styles of the individual characters that make up the symbolic notation of file permissions.
+#[cfg_attr(windows, allow(dead_code))]
+#[derive(Clone, Copy, Debug)]
+pub struct Permissions {
+    /// Absent permissions i.e. `-` as well as regular files.
+    pub none: Style,
+    pub dir: Style,
+    pub link: Style,
+    pub read: Style,
+    pub write: Style,
+
+    /// Execute permissions as well as the setuid, setgid, and sticky bits.
+    pub exec: Style,
+
+    /// The `@` that denotes the presence of extended attributes.
+    pub xattr: Style,
+}
+
+/// A single user-defined style that takes precedence over the one provided by the [Preset].
+#[derive(Clone, Debug)]
+pub struct Override {
+    key: Key,
+    style: Style,
+}
+
+/// The element of the output that an [Override] applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Key {
+    Tree,
+    Link,
+    BrokenLink,
+    Mount,
+    Xattrs,
+    Audit,
+    Incomplete,
+    Placeholder,
+    SparseRatio,
+    OctalPermissions,
+    Ino,
+    Owner,
+    Group,
+    Nlink,
+    Blocks,
+    Datetime,
+    Size(usize),
+    PermNone,
+    PermDir,
+    PermLink,
+    PermRead,
+    PermWrite,
+    PermExec,
+    PermXattr,
+}
+
+/// Names of the size units in the order that they appear in [`Palette::sizes`].
+pub const SIZE_UNITS: [&str; 6] = ["b", "kb", "mb", "gb", "tb", "pb"];
+
+impl Palette {
+    /// The styles that make up a built-in [Preset].
+    pub fn preset(preset: Preset) -> Self {
+        match preset {
+            Preset::Default => Self::default_preset(),
+            Preset::Nord => Self::nord(),
+            Preset::Dracula => Self::dracula(),
+            Preset::Solarized => Self::solarized(),
+            Preset::Monochrome => Self::monochrome(),
+        }
+    }
+
+    /// Replaces styles of the palette with the user-defined `overrides`. Later overrides take
+    /// precedence over earlier ones that share a key.
+    pub fn apply(&mut self, overrides: &[Override]) {
+        for Override { key, style } in overrides {
+            let style = *style;
+
+            match key {
+                Key::Tree => self.tree = style,
+                Key::Link => self.link = style,
+                Key::BrokenLink => self.broken_link = style,
+                Key::Mount => self.mount = style,
+                Key::Xattrs => self.xattrs = style,
+                Key::Audit => self.audit = style,
+                Key::Incomplete => self.incomplete = style,
+                Key::Placeholder => self.placeholder = style,
+                Key::SparseRatio => self.sparse_ratio = style,
+                Key::OctalPermissions => self.octal_permissions = style,
+                Key::Ino => self.ino = style,
+                Key::Owner => self.owner = style,
+                Key::Group => self.group = style,
+                Key::Nlink => self.nlink = style,
+                Key::Blocks => self.blocks = style,
+                Key::Datetime => self.datetime = style,
+                Key::Size(i) => self.sizes[*i] = style,
+                Key::PermNone => self.permissions.none = style,
+                Key::PermDir => self.permissions.dir = style,
+                Key::PermLink => self.permissions.link = style,
+                Key::PermRead => self.permissions.read = style,
+                Key::PermWrite => self.permissions.write = style,
+                Key::PermExec => self.permissions.exec = style,
+                Key::PermXattr => self.permissions.xattr = style,
+            }
+        }
+    }
+
+    /// Style of the size unit given by its prefix e.g. `"KiB"` or `"MB"`.
+    pub fn size(&self, unit: &str) -> Style {
+        let unit = unit.to_ascii_lowercase().replace('i', "");
+
+        SIZE_UNITS
+            .iter()
+            .position(|u| *u == unit)
+            .map_or_else(Style::default, |i| self.sizes[i])
+    }
+
+    fn default_preset() -> Self {
+        Self {
+            tree: Color::Purple.normal(),
+            link: Color::Red.normal(),
+            broken_link: Color::Red.bold().underline(),
+            mount: Color::Cyan.normal(),
+            xattrs: Color::Blue.normal(),
+            audit: Color::Red.bold().reverse(),
+            incomplete: Color::Yellow.bold(),
+            placeholder: Color::Purple.normal(),
+            sparse_ratio: Color::Yellow.bold(),
+            octal_permissions: Color::Purple.bold(),
+            ino: Color::Cyan.bold(),
+            owner: Color::Cyan.bold(),
+            group: Color::Green.bold(),
+            nlink: Color::Red.bold(),
+            blocks: Color::Blue.bold(),
+            datetime: Color::Purple.bold(),
+            sizes: [
+                Color::Cyan.bold(),
+                Color::Yellow.bold(),
+                Color::Green.bold(),
+                Color::Red.bold(),
+                Color::Blue.bold(),
+                Color::Purple.bold(),
+            ],
+            permissions: Permissions {
+                none: Color::Purple.normal(),
+                dir: Color::Blue.bold(),
+                link: Color::Red.bold(),
+                read: Color::Green.bold(),
+                write: Color::Yellow.bold(),
+                exec: Color::Red.bold(),
+                xattr: Color::Cyan.bold(),
+            },
+        }
+    }
+
+    fn nord() -> Self {
+        let frost = Color::RGB(0x88, 0xC0, 0xD0);
+        let blue = Color::RGB(0x81, 0xA1, 0xC1);
+        let red = Color::RGB(0xBF, 0x61, 0x6A);
+        let orange = Color::RGB(0xD0, 0x87, 0x70);
+        let yellow = Color::RGB(0xEB, 0xCB, 0x8B);
+        let green = Color::RGB(0xA3, 0xBE, 0x8C);
+        let purple = Color::RGB(0xB4, 0x8E, 0xAD);
+        let grey = Color::RGB(0x4C, 0x56, 0x6A);
+
+        Self::from_colors(
+            frost,
+            red,
+            grey,
+            [frost, blue, green, yellow, orange, purple, red],
+        )
+    }
+
+    fn dracula() -> Self {
+        let purple = Color::RGB(0xBD, 0x93, 0xF9);
+        let pink = Color::RGB(0xFF, 0x79, 0xC6);
+        let cyan = Color::RGB(0x8B, 0xE9, 0xFD);
+        let green = Color::RGB(0x50, 0xFA, 0x7B);
+        let yellow = Color::RGB(0xF1, 0xFA, 0x8C);
+        let orange = Color::RGB(0xFF, 0xB8, 0x6C);
+        let red = Color::RGB(0xFF, 0x55, 0x55);
+        let comment = Color::RGB(0x62, 0x72, 0xA4);
+
+        Self::from_colors(
+            purple,
+            pink,
+            comment,
+            [cyan, purple, green, yellow, orange, pink, red],
+        )
+    }
+
+    fn solarized() -> Self {
+        let blue = Color::RGB(0x26, 0x8B, 0xD2);
+        let cyan = Color::RGB(0x2A, 0xA1, 0x98);
+        let green = Color::RGB(0x85, 0x99, 0x00);
+        let yellow = Color::RGB(0xB5, 0x89, 0x00);
+        let orange = Color::RGB(0xCB, 0x4B, 0x16);
+        let red = Color::RGB(0xDC, 0x32, 0x2F);
+        let violet = Color::RGB(0x6C, 0x71, 0xC4);
+        let base01 = Color::RGB(0x58, 0x6E, 0x75);
+
+        Self::from_colors(
+            base01,
+            orange,
+            base01,
+            [cyan, blue, green, yellow, orange, violet, red],
+        )
+    }
+
+    fn monochrome() -> Self {
+        let bold = Style::new().bold();
+        let dimmed = Style::new().dimmed();
+        let plain = Style::new();
+
+        Self {
+            tree: dimmed,
+            link: Style::new().italic(),
+            broken_link: Style::new().bold().underline(),
+            mount: Style::new().italic(),
+            xattrs: Style::new().dimmed(),
+            audit: Style::new().reverse(),
+            incomplete: bold,
+            placeholder: dimmed,
+            sparse_ratio: bold,
+            octal_permissions: plain,
+            ino: dimmed,
+            owner: plain,
+            group: plain,
+            nlink: dimmed,
+            blocks: plain,
+            datetime: plain,
+            sizes: [dimmed, plain, plain, bold, bold, bold],
+            permissions: Permissions {
+                none: dimmed,
+                dir: bold,
+                link: Style::new().italic(),
+                read: plain,
+                write: plain,
+                exec: bold,
+                xattr: dimmed,
+            },
+        }
+    }
+
+    /// Builds a palette out of a `branch` color for tree branches, a `link` color for branches of
+    /// followed symlinks, a `muted` color for placeholders and empty permissions, and an
+    /// `accents` ramp that is spread across the remaining styles.
+    fn from_colors(branch: Color, link: Color, muted: Color, accents: [Color; 7]) -> Self {
+        let [a0, a1, a2, a3, a4, a5, a6] = accents;
+
+        Self {
+            tree: branch.normal(),
+            link: link.normal(),
+            broken_link: a6.bold().underline(),
+            mount: a1.normal(),
+            xattrs: a4.normal(),
+            audit: a6.reverse(),
+            incomplete: a3.bold(),
+            placeholder: muted.normal(),
+            sparse_ratio: a3.bold(),
+            octal_permissions: a5.bold(),
+            ino: a0.normal(),
+            owner: a0.bold(),
+            group: a2.bold(),
+            nlink: a6.normal(),
+            blocks: a1.bold(),
+            datetime: a5.normal(),
+            sizes: [
+                a0.bold(),
+                a3.bold(),
+                a2.bold(),
+                a4.bold(),
+                a6.bold(),
+                a5.bold(),
+            ],
+            permissions: Permissions {
+                none: muted.normal(),
+                dir: a1.bold(),
+                link: a6.bold(),
+                read: a2.bold(),
+                write: a3.bold(),
+                exec: a6.bold(),
+                xattr: a0.bold(),
+            },
+        }
+    }
+}
+
+/// Parses an [Override] of the form `KEY=STYLE` e.g. `size.mb=bold #ff8800`.
+pub fn parse_override(arg: &str) -> Result<Override, String> {
+    let (key, spec) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=STYLE but found '{arg}'"))?;
+
+    let key = parse_key(key.trim())?;
+    let style = parse_style(spec)?;
+
+    Ok(Override { key, style })
+}
+
+/// Parses the name of a configurable style.
+fn parse_key(key: &str) -> Result<Key, String> {
+    let key = match key.to_ascii_lowercase().as_str() {
+        "tree" => Key::Tree,
+        "link" => Key::Link,
+        "broken_link" => Key::BrokenLink,
+        "mount" => Key::Mount,
+        "xattrs" => Key::Xattrs,
+        "audit" => Key::Audit,
+        "incomplete" => Key::Incomplete,
+        "placeholder" => Key::Placeholder,
+        "sparse_ratio" => Key::SparseRatio,
+        "octal_permissions" => Key::OctalPermissions,
+        "ino" => Key::Ino,
+        "owner" => Key::Owner,
+        "group" => Key::Group,
+        "nlink" => Key::Nlink,
+        "blocks" => Key::Blocks,
+        "datetime" => Key::Datetime,
+        "permissions.none" => Key::PermNone,
+        "permissions.dir" => Key::PermDir,
+        "permissions.link" => Key::PermLink,
+        "permissions.read" => Key::PermRead,
+        "permissions.write" => Key::PermWrite,
+        "permissions.exec" => Key::PermExec,
+        "permissions.xattr" => Key::PermXattr,
+        other => other
+            .strip_prefix("size.")
+            .and_then(|unit| SIZE_UNITS.iter().position(|u| *u == unit))
+            .map(Key::Size)
+            .ok_or_else(|| format!("unknown style '{key}'"))?,
+    };
+
+    Ok(key)
+}
+
+/// Parses a whitespace separated style specification made up of modifiers such as `bold` or
+/// `underline` and up to two colors, the second of which must be preceded by `on` and is used as
+/// the background. Colors may be named, a 256-color code from `0` to `255`, or a truecolor hex
+/// code such as `#ff8800`. The specification `none` results in unstyled output.
+pub fn parse_style(spec: &str) -> Result<Style, String> {
+    let mut style = Style::new();
+    let mut tokens = spec.split_whitespace().peekable();
+
+    if tokens.peek().is_none() {
+        return Err(String::from("style must not be empty"));
+    }
+
+    while let Some(token) = tokens.next() {
+        match token.to_ascii_lowercase().as_str() {
+            "none" | "plain" => (),
+            "bold" => style = style.bold(),
+            "dimmed" | "dim" => style = style.dimmed(),
+            "italic" => style = style.italic(),
+            "underline" => style = style.underline(),
+            "blink" => style = style.blink(),
+            "reverse" => style = style.reverse(),
+            "hidden" => style = style.hidden(),
+            "strikethrough" => style = style.strikethrough(),
+            "on" => {
+                let color = tokens
+                    .next()
+                    .ok_or_else(|| String::from("expected a background color after 'on'"))?;
+                style = style.on(parse_color(color)?);
+            },
+            _ if style.foreground.is_none() => style = style.fg(parse_color(token)?),
+            _ => return Err(format!("unexpected '{token}' in style '{spec}'")),
+        }
+    }
+
+    Ok(style)
+}
+
+/// Parses a named color, a 256-color code, or a truecolor hex code.
+fn parse_color(color: &str) -> Result<Color, String> {
+    let named = match color.to_ascii_lowercase().as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "purple" | "magenta" => Some(Color::Purple),
+        "cyan" => Some(Color::Cyan),
+        "white" => Some(Color::White),
+        _ => None,
+    };
+
+    if let Some(color) = named {
+        return Ok(color);
+    }
+
+    if let Some(hex) = color.strip_prefix('#') {
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|c| u8::from_str_radix(c, 16).ok())
+        };
+
+        return match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Color::RGB(r, g, b)),
+            _ => Err(format!(
+                "invalid truecolor '{color}'; expected the form #rrggbb"
+            )),
+        };
+    }
+
+    color
+        .parse::<u8>()
+        .map(Color::Fixed)
+        .map_err(|_e| format!("unknown color '{color}'"))
+}
+
+#[test]
+fn test_parse_style() {
+    assert_eq!(parse_style("bold red"), Ok(Color::Red.bold()));
+    assert_eq!(parse_style("208"), Ok(Color::Fixed(208).normal()));
+    assert_eq!(
+        parse_style("underline #FF8800 on black"),
+        Ok(Color::RGB(0xFF, 0x88, 0x00).underline().on(Color::Black))
+    );
+    assert_eq!(parse_style("none"), Ok(Style::new()));
+
+    assert!(parse_style("").is_err());
+    assert!(parse_style("red blue").is_err());
+    assert!(parse_style("256").is_err());
+    assert!(parse_style("#ff88").is_err());
+    assert!(parse_style("bold on").is_err());
+}
+
+#[test]
+fn test_apply_overrides() -> Result<(), String> {
+    let mut palette = Palette::preset(Preset::Default);
+
+    let overrides = [
+        "tree=blue",
+        "size.mb=bold 196",
+        "permissions.exec=green",
+        "tree=cyan",
+    ]
+    .into_iter()
+    .map(parse_override)
+    .collect::<Result<Vec<_>, _>>()?;
+
+    palette.apply(&overrides);
+
+    assert_eq!(palette.tree, Color::Cyan.normal());
+    assert_eq!(palette.size("MiB"), Color::Fixed(196).bold());
+    assert_eq!(palette.size("MB"), Color::Fixed(196).bold());
+    assert_eq!(palette.permissions.exec, Color::Green.normal());
+
+    assert!(parse_override("size.eb=red").is_err());
+    assert!(parse_override("tree").is_err());
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use super::Node;
+use crate::fs::kind::Kind;
 use std::{
     convert::From,
     fmt::{self, Display},
@@ -6,12 +7,18 @@ use std::{
 };
 
 /// For keeping track of the number of various file-types of [Node]'s chlidren.
-#[allow(clippy::module_name_repetitions)]
-#[derive(Default)]
+#[allow(clippy::module_name_repetitions, clippy::struct_field_names)]
+#[derive(Clone, Copy, Default)]
 pub struct FileCount {
     pub num_dirs: usize,
     pub num_files: usize,
     pub num_links: usize,
+    pub num_broken_links: usize,
+    pub num_executables: usize,
+    pub num_sockets: usize,
+    pub num_fifos: usize,
+    pub num_block_devices: usize,
+    pub num_char_devices: usize,
 }
 
 impl AddAssign<&Node> for FileCount {
@@ -19,33 +26,28 @@ impl AddAssign<&Node> for FileCount {
     fn add_assign(&mut self, rhs: &Node) {
         if rhs.is_dir() {
             self.num_dirs += 1;
+        } else if rhs.is_broken_link() {
+            self.num_broken_links += 1;
         } else if rhs.is_symlink() {
             self.num_links += 1;
         } else {
-            self.num_files += 1;
+            match rhs.kind() {
+                Some(Kind::Executable) => self.num_executables += 1,
+                Some(Kind::Socket) => self.num_sockets += 1,
+                Some(Kind::Fifo) => self.num_fifos += 1,
+                Some(Kind::BlockDevice) => self.num_block_devices += 1,
+                Some(Kind::CharDevice) => self.num_char_devices += 1,
+                _ => self.num_files += 1,
+            }
         }
     }
 }
 impl Add<&Node> for FileCount {
     type Output = Self;
     /// Update [Self] with information from [Node].
-    fn add(self, rhs: &Node) -> Self::Output {
-        if rhs.is_dir() {
-            Self {
-                num_dirs: self.num_dirs + 1,
-                ..self
-            }
-        } else if rhs.is_symlink() {
-            Self {
-                num_links: self.num_links + 1,
-                ..self
-            }
-        } else {
-            Self {
-                num_files: self.num_files + 1,
-                ..self
-            }
-        }
+    fn add(mut self, rhs: &Node) -> Self::Output {
+        self += rhs;
+        self
     }
 }
 
@@ -55,17 +57,20 @@ impl AddAssign for FileCount {
         self.num_dirs += rhs.num_dirs;
         self.num_links += rhs.num_links;
         self.num_files += rhs.num_files;
+        self.num_broken_links += rhs.num_broken_links;
+        self.num_executables += rhs.num_executables;
+        self.num_sockets += rhs.num_sockets;
+        self.num_fifos += rhs.num_fifos;
+        self.num_block_devices += rhs.num_block_devices;
+        self.num_char_devices += rhs.num_char_devices;
     }
 }
 impl Add for FileCount {
     type Output = Self;
     /// Add [Self] with information from another [Self].
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            num_dirs: self.num_dirs + rhs.num_dirs,
-            num_links: self.num_links + rhs.num_links,
-            num_files: self.num_files + rhs.num_files,
-        }
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
     }
 }
 
@@ -77,42 +82,31 @@ impl From<Vec<Self>> for FileCount {
 }
 
 impl Display for FileCount {
+    /// Only non-zero counts are displayed, e.g. `3 directories, 6 files, 1 broken link`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut components = vec![];
+        let counts = [
+            (self.num_dirs, "directory", "directories"),
+            (self.num_files, "file", "files"),
+            (self.num_executables, "executable", "executables"),
+            (self.num_links, "link", "links"),
+            (self.num_broken_links, "broken link", "broken links"),
+            (self.num_sockets, "socket", "sockets"),
+            (self.num_fifos, "fifo", "fifos"),
+            (self.num_block_devices, "block device", "block devices"),
+            (
+                self.num_char_devices,
+                "character device",
+                "character devices",
+            ),
+        ];
 
-        if self.num_dirs > 0 {
-            let output = format!(
-                "{} {}",
-                self.num_dirs,
-                if self.num_dirs > 1 {
-                    "directories"
-                } else {
-                    "directory"
-                }
-            );
-
-            components.push(output);
-        }
-
-        if self.num_files > 0 {
-            let output = format!(
-                "{} {}",
-                self.num_files,
-                if self.num_files > 1 { "files" } else { "file" }
-            );
-
-            components.push(output);
-        }
-
-        if self.num_links > 0 {
-            let output = format!(
-                "{} {}",
-                self.num_links,
-                if self.num_links > 1 { "links" } else { "link" }
-            );
-
-            components.push(output);
-        }
+        let components = counts
+            .into_iter()
+            .filter(|(count, ..)| *count > 0)
+            .map(|(count, singular, plural)| {
+                format!("{count} {}", if count > 1 { plural } else { singular })
+            })
+            .collect::<Vec<_>>();
 
         write!(f, "{}", components.join(", "))
     }
@@ -0,0 +1,110 @@
+use crate::{context::Context, progress::Message, tty};
+use std::{
+    fmt::{self, Display},
+    process,
+    sync::{mpsc::SyncSender, OnceLock},
+    thread,
+    time::Duration,
+};
+
+/// Why traversal was cut short, in which case the output only reflects what was read until then.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cutoff {
+    /// The user sent an interrupt, i.e. Ctrl-C.
+    Interrupted,
+
+    /// Traversal ran for longer than `--timeout`.
+    TimedOut(Duration),
+}
+
+/// How traversal ended, which is settled by whatever happens first: an interrupt, `--timeout`
+/// elapsing, or traversal completing, in which case it's `None`.
+static OUTCOME: OnceLock<Option<Cutoff>> = OnceLock::new();
+
+/// The exit status of a process that was terminated by `SIGINT`.
+const INTERRUPTED_STATUS: u8 = 130;
+
+/// Returns `true` if traversal may be cut short, in which case what's needed to tell which
+/// directories weren't read in full is tracked. That's the case if `--timeout` is provided or if
+/// output goes to a terminal, as interrupts are otherwise left to terminate the process.
+pub const fn is_possible(ctx: &Context) -> bool {
+    ctx.timeout.is_some() || ctx.stdout_is_tty
+}
+
+/// Registers the interrupt handler if output goes to a terminal and starts the clock on
+/// `--timeout`, if provided. The first interrupt cuts traversal short so that whatever was
+/// gathered is still shown whereas an interrupt after that, or after traversal completed, exits
+/// right away. `mailbox` is that of the progress indicator, if any, which is torn down before
+/// exiting.
+pub fn init(ctx: &Context, mailbox: Option<SyncSender<Message>>) {
+    let int_handler = move || {
+        if cut(Cutoff::Interrupted) {
+            return;
+        }
+
+        if let Some(ref mailbox) = mailbox {
+            let _ = mailbox.try_send(Message::Finish);
+            tty::restore();
+        }
+
+        process::exit(i32::from(INTERRUPTED_STATUS));
+    };
+
+    if ctx.stdout_is_tty {
+        ctrlc::set_handler(int_handler).expect("Failed to set interrupt handler");
+    }
+
+    if let Some(timeout) = ctx.timeout {
+        if timeout.is_zero() {
+            cut(Cutoff::TimedOut(timeout));
+            return;
+        }
+
+        thread::spawn(move || {
+            thread::sleep(timeout);
+            cut(Cutoff::TimedOut(timeout));
+        });
+    }
+}
+
+/// Cuts traversal short for the given reason. Returns `false` if it already was or if traversal
+/// already completed.
+pub fn cut(cutoff: Cutoff) -> bool {
+    OUTCOME.set(Some(cutoff)).is_ok()
+}
+
+/// Returns `true` if traversal is to stop as soon as possible.
+pub fn is_cut() -> bool {
+    matches!(OUTCOME.get(), Some(Some(_)))
+}
+
+/// Settles how traversal ended, which is expected to be called once it stops. Returns why it was
+/// cut short if it was.
+pub fn finish() -> Option<Cutoff> {
+    *OUTCOME.get_or_init(|| None)
+}
+
+impl Cutoff {
+    /// Returns the exit status appropriate for the [Cutoff], if any other than success.
+    pub const fn exit_status(self) -> Option<u8> {
+        match self {
+            Self::Interrupted => Some(INTERRUPTED_STATUS),
+            Self::TimedOut(_) => None,
+        }
+    }
+}
+
+impl Display for Cutoff {
+    /// The notice that follows the output of a traversal that was cut short.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Interrupted => write!(f, "Traversal was interrupted")?,
+            Self::TimedOut(timeout) => write!(f, "Traversal timed out after {timeout:?}")?,
+        }
+
+        write!(
+            f,
+            "; totals and the sizes of incomplete directories are lower bounds"
+        )
+    }
+}
@@ -19,6 +19,9 @@ pub enum Error {
     #[error("File expected to have parent")]
     ExpectedParent,
 
+    #[error("Failed to load ignore file {0}")]
+    IgnoreFile(String),
+
     #[error("Invalid glob patterns: {0}")]
     InvalidGlobPatterns(#[from] IgnoreError),
 
@@ -0,0 +1,111 @@
+use super::{count::FileCount, mount, node::Node};
+use crate::{
+    context::Context,
+    disk_usage::file_size::{sloc, DiskUsage, FileSize},
+    fs::mount::{self as fs_mount, Mount},
+};
+use std::path::Path;
+
+#[cfg(unix)]
+use crate::fs::permissions::audit;
+
+/// Tallies of the entries beyond the maximum depth of the output that were folded into the sizes
+/// of their displayed ancestors during traversal rather than kept in the [`Tree`]. These keep the
+/// summaries that account for every entry accurate.
+///
+/// [`Tree`]: super::Tree
+#[derive(Default)]
+pub struct Folded {
+    /// Number of entries that were folded.
+    len: usize,
+
+    /// Counts of each file-type that was folded.
+    pub file_count: FileCount,
+
+    /// Source lines of code of the folded files.
+    pub sloc: sloc::Summary,
+
+    /// Findings of the folded files if they were audited.
+    #[cfg(unix)]
+    pub audit: audit::Summary,
+
+    /// Totals of the folded files of each filesystem.
+    pub mounts: mount::Summary,
+
+    /// Whether or not any of the folded directories is a filesystem that traversal crossed onto.
+    pub crossed_mount: bool,
+
+    /// Whether or not traversal crossed onto a filesystem other than that of the root anywhere in
+    /// the tree, including directories that were kept.
+    crossed_any_mount: bool,
+
+    /// The filesystem on which the root resides.
+    root_mount: Option<&'static Mount>,
+}
+
+impl Folded {
+    /// Initializes empty tallies for the traversal of the root of the [Context].
+    pub fn new(ctx: &Context) -> Self {
+        Self {
+            root_mount: fs_mount::containing(ctx.dir_canonical()),
+            ..Self::default()
+        }
+    }
+
+    /// Takes note of whether `node`, which may or may not be folded, is a filesystem other than
+    /// that of the root onto which traversal crossed. Expected to be called for every [Node] in
+    /// the order of traversal.
+    pub fn observe(&mut self, node: &Node) {
+        if node.depth() > 0 && crossed_mount(node).is_some() {
+            self.crossed_any_mount = true;
+        }
+    }
+
+    /// Accounts for the [Node] at `path`. Only directories are accounted for if everything else
+    /// is to be filtered out of the [`Tree`].
+    ///
+    /// [`Tree`]: super::Tree
+    pub fn add(&mut self, node: &Node, path: &Path, ctx: &Context) {
+        if ctx.dirs_only && !node.is_dir() {
+            return;
+        }
+
+        // Nothing beneath a filesystem that isn't crossed is traversed, so the filesystem that
+        // contains the parent is that of the nearest crossed mount. Looking it up is only
+        // necessary once traversal has crossed onto another filesystem.
+        let mount = if let Some(mount) = crossed_mount(node) {
+            self.crossed_mount = true;
+            Some(mount)
+        } else if self.crossed_any_mount {
+            path.parent().and_then(fs_mount::containing)
+        } else {
+            self.root_mount
+        };
+
+        self.len += 1;
+        self.file_count += node;
+
+        if let Some(FileSize::Sloc(metric)) = node.file_size_of(DiskUsage::Sloc) {
+            self.sloc.add(metric);
+        }
+
+        #[cfg(unix)]
+        self.audit.add(node.findings());
+
+        if let Some(mount) = mount {
+            self.mounts.add(mount, node, ctx);
+        }
+    }
+
+    /// Whether or not nothing was folded.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// The filesystem mounted on `node` if traversal crossed onto it.
+fn crossed_mount(node: &Node) -> Option<&'static Mount> {
+    node.mount_point()
+        .filter(|mount_point| mount_point.crossed)
+        .map(|mount_point| mount_point.mount)
+}
@@ -1,13 +1,18 @@
 use crate::{
     context::{column, Context},
-    disk_usage::file_size::FileSize,
+    disk_usage::file_size::{self, sloc, DiskUsage, FileSize},
     fs::inode::Inode,
     progress::{IndicatorHandle, Message},
     utils,
 };
 use count::FileCount;
+use cutoff::Cutoff;
+use fold::Folded;
+
+#[cfg(unix)]
+use crate::fs::permissions::audit;
 use error::Error;
-use ignore::{WalkBuilder, WalkParallel};
+use ignore::{DirEntry, WalkBuilder, WalkParallel};
 use indextree::{Arena, NodeId};
 use node::{cmp::NodeComparator, Node};
 use std::{
@@ -24,27 +29,56 @@ use visitor::{BranchVisitorBuilder, TraversalState};
 /// Operations to handle and display aggregate file counts based on their type.
 pub mod count;
 
+/// Cutting traversal short on account of an interrupt or `--timeout`.
+pub mod cutoff;
+
 /// Errors related to traversal, [Tree] construction, and the like.
 pub mod error;
 
+/// Tallies of the entries that were folded into their ancestors rather than kept in the [Tree].
+pub mod fold;
+
+/// Per-filesystem totals for when traversal crosses mount points.
+pub mod mount;
+
 /// Contains components of the [`Tree`] data structure that derive from [`ignore::DirEntry`].
 pub mod node;
 
+/// Traversal that reads directories with `getdents64(2)` and queries files with `statx(2)`.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+mod scan;
+
 /// Custom visitor that operates on each thread during filesystem traversal.
 mod visitor;
 
+/// Name of the erdtree-specific ignore file which is respected on a per directory basis in the same
+/// manner as `.gitignore`.
+const ERDTREE_IGNORE: &str = ".erdignore";
+
 /// Virtual data structure that represents local file-system hierarchy.
 pub struct Tree {
     arena: Arena<Node>,
     root_id: NodeId,
+    folded: Folded,
+    cutoff: Option<Cutoff>,
 }
 
 pub type Result<T> = StdResult<T, Error>;
 
 impl Tree {
     /// Constructor for [Tree].
-    pub const fn new(arena: Arena<Node>, root_id: NodeId) -> Self {
-        Self { arena, root_id }
+    pub const fn new(
+        arena: Arena<Node>,
+        root_id: NodeId,
+        folded: Folded,
+        cutoff: Option<Cutoff>,
+    ) -> Self {
+        Self {
+            arena,
+            root_id,
+            folded,
+            cutoff,
+        }
     }
 
     /// Initiates file-system traversal and [Tree] as well as updates the [Context] object with
@@ -55,7 +89,8 @@ impl Tree {
     ) -> Result<(Self, Context)> {
         let mut column_properties = column::Properties::from(&ctx);
 
-        let (arena, root_id) = Self::traverse(&ctx, &mut column_properties, indicator)?;
+        let (arena, root_id, folded, cutoff) =
+            Self::traverse(&ctx, &mut column_properties, indicator)?;
 
         ctx.update_column_properties(&column_properties);
 
@@ -63,9 +98,10 @@ impl Tree {
             ctx.set_window_width();
         }
 
-        let tree = Self::new(arena, root_id);
+        let tree = Self::new(arena, root_id, folded, cutoff);
 
-        if tree.is_stump() {
+        // If traversal was cut short there may well have been entries to show had it not been.
+        if tree.is_stump() && cutoff.is_none() {
             return Err(Error::NoMatches);
         }
 
@@ -80,6 +116,7 @@ impl Tree {
             .peekable()
             .next()
             .is_none()
+            && self.folded.is_empty()
     }
 
     /// Grab a reference to `root_id`.
@@ -92,15 +129,52 @@ impl Tree {
         &self.arena
     }
 
+    /// Tallies of the entries beyond the maximum depth of the output that aren't in `arena`.
+    pub const fn folded(&self) -> &Folded {
+        &self.folded
+    }
+
+    /// Why traversal was cut short, if it was.
+    pub const fn cutoff(&self) -> Option<Cutoff> {
+        self.cutoff
+    }
+
+    /// The path of the node at `node_id` relative to the root, which is reassembled from the file
+    /// names of its ancestors. The path of the root itself is its file name.
+    pub fn relative_path(&self, node_id: NodeId) -> PathBuf {
+        if node_id == self.root_id {
+            return PathBuf::from(self.arena[node_id].get().file_name());
+        }
+
+        let mut names = node_id
+            .ancestors(&self.arena)
+            .take_while(|&id| id != self.root_id)
+            .map(|id| self.arena[id].get().file_name())
+            .collect::<Vec<_>>();
+
+        names.reverse();
+        names.into_iter().collect()
+    }
+
     /// Parallel traversal of the `root_id` directory and its contents. Parallel traversal relies on
     /// `WalkParallel`. Any filesystem I/O or related system calls are expected to occur during
     /// parallel traversal; post-processing post-processing of all directory entries should
     /// be completely CPU-bound.
+    ///
+    /// If [`Context::folds_beyond_level`], entries deeper than the maximum depth of the output
+    /// are folded into their ancestor at that depth as they arrive and then discarded, so that
+    /// the size of the tree is proportional to what is printed.
+    ///
+    /// If traversal is cut short, directories that weren't read in full are marked as such along
+    /// with their ancestors. To tell which, each directory is expected to be followed by the
+    /// number of its entries that were queued, and every entry that arrives is subtracted from
+    /// what's outstanding of its parent or of its displayed ancestor if it's folded.
+    #[allow(clippy::too_many_lines)]
     fn traverse(
         ctx: &Context,
         column_properties: &mut column::Properties,
         indicator: Option<&IndicatorHandle>,
-    ) -> Result<(Arena<Node>, NodeId)> {
+    ) -> Result<(Arena<Node>, NodeId, Folded, Option<Cutoff>)> {
         let walker = WalkParallel::try_from(ctx)?;
         let (tx, rx) = mpsc::channel();
 
@@ -108,43 +182,112 @@ impl Tree {
 
         thread::scope(|s| {
             let res = s.spawn(move || {
-                let mut tree = Arena::new();
-                let mut branches: HashMap<PathBuf, Vec<NodeId>> = HashMap::new();
+                let mut tree: Arena<Node> = Arena::new();
                 let mut root_id = None;
+                let mut inodes = HashSet::new();
+
+                let fold = ctx.folds_beyond_level();
+                let level = ctx.level();
+                let mut folded = Folded::new(ctx);
+
+                // Only directories are tracked by path as they're the only possible parents. The
+                // paths are discarded once traversal completes as nodes only retain file names.
+                let mut dirs: HashMap<PathBuf, NodeId> = HashMap::new();
+
+                // Entries yet to arrive beneath each directory, including those of its folded
+                // descendants. Only of consequence if traversal is cut short.
+                let mut outstanding: HashMap<NodeId, isize> = HashMap::new();
+                let track = cutoff::is_possible(ctx);
+
+                loop {
+                    let (node, path) = match rx.recv() {
+                        Ok(TraversalState::Ongoing(node, path)) => (node, path),
+
+                        Ok(TraversalState::Listed(dir, queued)) => {
+                            if let Some(&dir_id) = dir.ancestors().find_map(|a| dirs.get(a)) {
+                                *outstanding.entry(dir_id).or_default() +=
+                                    isize::try_from(queued).unwrap_or(isize::MAX) - 1;
+                            }
+                            continue;
+                        },
+
+                        Ok(TraversalState::Done) | Err(_) => break,
+                    };
 
-                while let Ok(TraversalState::Ongoing(node)) = rx.recv() {
                     if let Some(ref mailbox) = progress_indicator_mailbox {
                         if mailbox.send(Message::Index).is_err() {
                             return Err(Error::Terminated);
                         }
                     }
 
-                    if node.is_dir() {
-                        let node_path = node.path();
+                    let is_dir = node.is_dir();
+                    let depth = node.depth();
 
-                        if !branches.contains_key(node_path) {
-                            branches.insert(node_path.to_owned(), vec![]);
-                        }
+                    folded.observe(&node);
 
-                        if node.depth() == 0 {
-                            root_id = Some(tree.new_node(node));
+                    if fold && depth > level {
+                        let Some(&ancestor_id) = path
+                            .ancestors()
+                            .nth(depth - level)
+                            .and_then(|ancestor| dirs.get(ancestor))
+                        else {
                             continue;
+                        };
+
+                        // The entry arrived but if it's a directory it has yet to be listed.
+                        if track && !is_dir {
+                            *outstanding.entry(ancestor_id).or_default() -= 1;
+                        }
+
+                        folded.add(&node, &path, ctx);
+
+                        // If a hard-link is already accounted for then don't add to its ancestor.
+                        if node
+                            .inode()
+                            .map_or(true, |inode| inode.nlink <= 1 || inodes.insert(inode))
+                        {
+                            tree[ancestor_id].get_mut().add_sizes_of(&node, ctx);
                         }
+
+                        continue;
+                    }
+
+                    if depth == 0 {
+                        let node_id = tree.new_node(node);
+                        root_id = Some(node_id);
+
+                        if track && is_dir {
+                            outstanding.insert(node_id, 1);
+                        }
+
+                        dirs.insert(path, node_id);
+                        continue;
                     }
 
-                    let parent = node.parent_path().ok_or(Error::ExpectedParent)?.to_owned();
+                    let parent = path.parent().ok_or(Error::ExpectedParent)?;
+
+                    let Some(&parent_id) = dirs.get(parent) else {
+                        continue;
+                    };
 
                     let node_id = tree.new_node(node);
+                    parent_id.append(node_id, &mut tree);
+
+                    if track {
+                        *outstanding.entry(parent_id).or_default() -= 1;
+                    }
+
+                    if is_dir {
+                        if track {
+                            *outstanding.entry(node_id).or_default() += 1;
+                        }
 
-                    if branches
-                        .get_mut(&parent)
-                        .map(|mut_ref| mut_ref.push(node_id))
-                        .is_none()
-                    {
-                        branches.insert(parent, vec![]);
+                        dirs.insert(path, node_id);
                     }
                 }
 
+                drop(dirs);
+
                 if let Some(ref mailbox) = progress_indicator_mailbox {
                     if mailbox.send(Message::DoneIndexing).is_err() {
                         return Err(Error::Terminated);
@@ -152,20 +295,25 @@ impl Tree {
                 }
 
                 let root_id = root_id.ok_or(Error::MissingRoot)?;
+
+                let cutoff = cutoff::finish();
+
+                if cutoff.is_some() {
+                    Self::mark_incomplete(&mut tree, &outstanding);
+                }
+
                 let node_comparator = node::cmp::comparator(ctx);
-                let mut inodes = HashSet::new();
 
                 Self::assemble_tree(
                     &mut tree,
                     root_id,
-                    &mut branches,
                     &node_comparator,
                     &mut inodes,
                     column_properties,
                     ctx,
                 );
 
-                if ctx.prune || ctx.pattern.is_some() {
+                if ctx.prunes() {
                     Self::prune_directories(root_id, &mut tree);
                 }
 
@@ -173,12 +321,19 @@ impl Tree {
                     Self::filter_directories(root_id, &mut tree);
                 }
 
-                Ok((tree, root_id))
+                #[cfg(unix)]
+                if ctx.audit_only {
+                    Self::filter_audited(root_id, &mut tree);
+                }
+
+                Ok((tree, root_id, folded, cutoff))
             });
 
-            let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(&tx));
+            Self::walk(ctx, walker, &tx);
 
-            walker.visit(&mut visitor_builder);
+            // Settled before the receiving end is done so that it sees the same outcome as
+            // traversal did rather than an interrupt that came after.
+            cutoff::finish();
 
             let _ = tx.send(TraversalState::Done);
 
@@ -186,23 +341,70 @@ impl Tree {
         })
     }
 
+    /// Sends every entry of the root directory that isn't ignored to the receiving end of `tx`,
+    /// reading directories with the getdents scanner if [`Context::scans_with_getdents`] and with
+    /// `walker` otherwise.
+    fn walk(ctx: &Context, walker: WalkParallel, tx: &Sender<TraversalState>) {
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        if let Some(scanner) = scan::Scanner::new(ctx) {
+            return scanner.scan(tx);
+        }
+
+        let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(tx));
+
+        walker.visit(&mut visitor_builder);
+    }
+
+    /// Marks the directories that still had `outstanding` entries once traversal was cut short
+    /// as incomplete along with their ancestors.
+    fn mark_incomplete(tree: &mut Arena<Node>, outstanding: &HashMap<NodeId, isize>) {
+        for (&dir_id, _) in outstanding.iter().filter(|(_, &count)| count > 0) {
+            let ancestors = dir_id.ancestors(tree).collect::<Vec<_>>();
+
+            for id in ancestors {
+                if tree[id].get().is_incomplete() {
+                    break;
+                }
+                tree[id].get_mut().mark_incomplete();
+            }
+        }
+    }
+
     /// Takes the results of the parallel traversal and uses it to construct the [Tree] data
     /// structure. Sorting occurs if specified. The amount of columns needed to fit all of the disk
     /// usages is also computed here.
     fn assemble_tree(
         tree: &mut Arena<Node>,
         current_node_id: NodeId,
-        branches: &mut HashMap<PathBuf, Vec<NodeId>>,
         node_comparator: &NodeComparator,
         inode_set: &mut HashSet<Inode>,
         column_properties: &mut column::Properties,
         ctx: &Context,
     ) {
-        let current_node = tree[current_node_id].get_mut();
+        let mut children = current_node_id.children(tree).collect::<Vec<_>>();
 
-        let mut children = branches.remove(current_node.path()).unwrap();
+        // Sizes of the descendants that were folded into the directory during traversal, if any.
+        let (folded_size, folded_sizes) = tree[current_node_id].get_mut().take_file_sizes();
 
-        let mut dir_size = FileSize::from(ctx);
+        let mut dir_size = folded_size.unwrap_or_else(|| FileSize::from(ctx));
+
+        let mut secondary_dir_sizes = ctx
+            .secondary_disk_usages()
+            .iter()
+            .copied()
+            .map(|disk_usage| {
+                let mut dir_size = FileSize::init_empty(disk_usage, ctx);
+
+                if let Some(folded_size) = folded_sizes
+                    .iter()
+                    .find(|file_size| file_size.disk_usage() == disk_usage)
+                {
+                    dir_size += folded_size;
+                }
+
+                dir_size
+            })
+            .collect::<Vec<_>>();
 
         for child_id in &children {
             let index = *child_id;
@@ -211,7 +413,6 @@ impl Tree {
                 Self::assemble_tree(
                     tree,
                     index,
-                    branches,
                     node_comparator,
                     inode_set,
                     column_properties,
@@ -233,6 +434,12 @@ impl Tree {
             if let Some(file_size) = node.file_size() {
                 dir_size += file_size;
             }
+
+            for secondary_dir_size in &mut secondary_dir_sizes {
+                if let Some(file_size) = node.file_size_of(secondary_dir_size.disk_usage()) {
+                    *secondary_dir_size += file_size;
+                }
+            }
         }
 
         if dir_size.value() > 0 {
@@ -241,6 +448,14 @@ impl Tree {
             dir.set_file_size(dir_size);
         }
 
+        secondary_dir_sizes.retain(|file_size| file_size.value() > 0);
+
+        if !secondary_dir_sizes.is_empty() {
+            let dir = tree[current_node_id].get_mut();
+
+            dir.set_secondary_file_sizes(secondary_dir_sizes);
+        }
+
         let dir = tree[current_node_id].get();
 
         Self::update_column_properties(column_properties, dir, ctx);
@@ -251,23 +466,22 @@ impl Tree {
             node_comparator(node_a, node_b)
         });
 
-        // Append children to current node.
+        // Re-append children to current node in sorted order.
         for child_id in children {
             current_node_id.append(child_id, tree);
         }
     }
 
-    /// Function to remove empty directories.
+    /// Function to remove empty directories, save for those that satisfy the metadata filters in
+    /// their own right.
     fn prune_directories(root_id: NodeId, tree: &mut Arena<Node>) {
         let to_prune = root_id
             .descendants(tree)
             .skip(1)
             .filter(|node_id| {
-                tree[*node_id]
-                    .get()
-                    .is_dir()
-                    .then(|| node_id.children(tree).count() == 0)
-                    .unwrap_or(false)
+                let node = tree[*node_id].get();
+
+                node.is_dir() && !node.is_matched() && node_id.children(tree).count() == 0
             })
             .collect::<Vec<_>>();
 
@@ -275,9 +489,9 @@ impl Tree {
             return;
         }
 
-        to_prune
-            .iter()
-            .for_each(|node_id| node_id.remove_subtree(tree));
+        for node_id in to_prune {
+            node_id.remove_subtree(tree);
+        }
 
         Self::prune_directories(root_id, tree);
     }
@@ -294,7 +508,37 @@ impl Tree {
             return;
         }
 
-        to_detach.iter().for_each(|node_id| node_id.detach(tree));
+        for node_id in to_detach {
+            node_id.detach(tree);
+        }
+    }
+
+    /// Filter `arena` for only entries flagged by an audit and the directories that contain them.
+    #[cfg(unix)]
+    fn filter_audited(root_id: NodeId, tree: &mut Arena<Node>) {
+        let mut to_keep = HashSet::new();
+
+        for node_id in root_id.descendants(tree) {
+            if !tree[node_id].get().findings().is_empty() {
+                to_keep.extend(node_id.ancestors(tree));
+            }
+        }
+
+        // Detaching the topmost entry of each subtree that is to be removed suffices.
+        let to_detach = root_id
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| {
+                !to_keep.contains(node_id)
+                    && tree[*node_id]
+                        .parent()
+                        .is_some_and(|parent_id| to_keep.contains(&parent_id))
+            })
+            .collect::<Vec<_>>();
+
+        for node_id in to_detach {
+            node_id.detach(tree);
+        }
     }
 
     /// Compute total number of files for a single directory without recurring into child
@@ -307,35 +551,141 @@ impl Tree {
             .fold(FileCount::default(), |acc, node| acc + node)
     }
 
+    /// Compute the per-language totals of source lines of code of every file in the tree,
+    /// including those beyond the maximum depth of the output.
+    pub fn compute_sloc_summary(&self) -> sloc::Summary {
+        let mut summary = self.folded.sloc.clone();
+
+        for node_id in self.root_id.descendants(&self.arena) {
+            if let Some(FileSize::Sloc(metric)) =
+                self.arena[node_id].get().file_size_of(DiskUsage::Sloc)
+            {
+                summary.add(metric);
+            }
+        }
+
+        summary
+    }
+
+    /// Tally the findings of an audit across every file in the tree, including those beyond the
+    /// maximum depth of the output.
+    #[cfg(unix)]
+    pub fn compute_audit_summary(&self) -> audit::Summary {
+        let mut summary = self.folded.audit.clone();
+
+        for node_id in self.root_id.descendants(&self.arena).skip(1) {
+            summary.add(self.arena[node_id].get().findings());
+        }
+
+        summary
+    }
+
+    /// Compute the totals of each filesystem that traversal descended into, including those beyond
+    /// the maximum depth of the output. Returns `None` if traversal never crossed onto a
+    /// filesystem other than that of the root.
+    pub fn compute_mount_summary(&self, ctx: &Context) -> Option<mount::Summary> {
+        let crossed_mount = |node_id: NodeId| {
+            self.arena[node_id]
+                .get()
+                .mount_point()
+                .filter(|mount_point| mount_point.crossed)
+                .map(|mount_point| mount_point.mount)
+        };
+
+        let mut descendants = self.root_id.descendants(&self.arena).skip(1);
+
+        if !self.folded.crossed_mount
+            && !descendants.any(|node_id| crossed_mount(node_id).is_some())
+        {
+            return None;
+        }
+
+        let root_mount = crate::fs::mount::containing(ctx.dir_canonical())?;
+
+        let mut summary = mount::Summary::default();
+
+        // Each node resides on the filesystem mounted on its nearest crossed mount point, which is
+        // carried down from parents to children.
+        let mut stack = vec![(self.root_id, root_mount)];
+
+        while let Some((node_id, parent_mount)) = stack.pop() {
+            let mount = crossed_mount(node_id).unwrap_or(parent_mount);
+
+            summary.add(mount, self.arena[node_id].get(), ctx);
+
+            stack.extend(
+                node_id
+                    .children(&self.arena)
+                    .map(|child_id| (child_id, mount)),
+            );
+        }
+
+        summary.merge(&self.folded.mounts, ctx);
+
+        Some(summary)
+    }
+
     /// Updates [`column::Properties`] with provided [`Node`].
     fn update_column_properties(col_props: &mut column::Properties, node: &Node, ctx: &Context) {
+        if ctx.gradient {
+            for file_size in node
+                .file_size()
+                .into_iter()
+                .chain(node.secondary_file_sizes())
+            {
+                let max_value = col_props
+                    .max_size_values
+                    .entry(file_size.disk_usage())
+                    .or_default();
+
+                if file_size.value() > *max_value {
+                    *max_value = file_size.value();
+                }
+            }
+        }
+
         if let Some(file_size) = node.file_size() {
-            if ctx.byte_metric() && ctx.human {
-                let out = format!("{file_size}");
-                let [size, unit]: [&str; 2] =
-                    out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
+            let (size_cols, unit_cols) = Self::file_size_cols(file_size, ctx);
+
+            if size_cols > col_props.max_size_width {
+                col_props.max_size_width = size_cols;
+            }
 
-                let file_size_cols = size.len();
-                let file_size_unit_cols = unit.len();
+            if unit_cols > col_props.max_size_unit_width {
+                col_props.max_size_unit_width = unit_cols;
+            }
+        }
 
-                if file_size_cols > col_props.max_size_width {
-                    col_props.max_size_width = file_size_cols;
-                }
+        for file_size in node.secondary_file_sizes() {
+            let (size_cols, unit_cols) = Self::file_size_cols(file_size, ctx);
 
-                if file_size_unit_cols > col_props.max_size_unit_width {
-                    col_props.max_size_unit_width = file_size_unit_cols;
-                }
-            } else {
-                let file_size_cols = utils::num_integral(file_size.value());
+            let widths = col_props
+                .secondary_size_widths
+                .entry(file_size.disk_usage())
+                .or_default();
+
+            if size_cols > widths.size {
+                widths.size = size_cols;
+            }
+
+            if unit_cols > widths.unit {
+                widths.unit = unit_cols;
+            }
+        }
 
-                if file_size_cols > col_props.max_size_width {
-                    col_props.max_size_width = file_size_cols;
+        if ctx.ratio {
+            if let Some(ratio) = node.size_ratio() {
+                // Accounts for the trailing '%'.
+                let ratio_cols = utils::num_integral(ratio).max(1) + 1;
+
+                if ratio_cols > col_props.max_ratio_width {
+                    col_props.max_ratio_width = ratio_cols;
                 }
-            };
+            }
         }
 
         #[cfg(unix)]
-        if ctx.long {
+        if ctx.long_view() {
             if let Some(owner) = node.owner() {
                 let owner_len = owner.len();
 
@@ -375,7 +725,40 @@ impl Tree {
                     col_props.max_block_width = blocks_num_integral;
                 }
             }
+
+            let time_format = ctx.time_format();
+
+            if time_format.width().is_none() {
+                for &stamp in ctx.times() {
+                    let Some(time) = node.timestamp(stamp) else {
+                        continue;
+                    };
+
+                    let time_len = time_format.format(time, ctx.time_zone()).chars().count();
+
+                    if time_len > col_props.max_time_width {
+                        col_props.max_time_width = time_len;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Computes the amount of columns needed for the value of the provided [`FileSize`] as well as
+    /// its unit. Unit columns are zero for metrics that are reported without a unit.
+    fn file_size_cols(file_size: &FileSize, ctx: &Context) -> (usize, usize) {
+        if let FileSize::Binary(_) = file_size {
+            return (file_size::BINARY.len(), 0);
+        }
+
+        if file_size.disk_usage().is_byte_metric() && ctx.human_readable() {
+            let out = format!("{file_size}");
+            let [size, unit]: [&str; 2] = out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
+
+            return (size.len(), unit.len());
         }
+
+        (utils::num_integral(file_size.value()), 0)
     }
 }
 
@@ -399,18 +782,78 @@ impl TryFrom<&Context> for WalkParallel {
             .same_file_system(ctx.same_fs)
             .threads(ctx.threads);
 
+        if !ctx.no_ignore {
+            builder.add_custom_ignore_filename(ERDTREE_IGNORE);
+        }
+
+        for ignore_file in &ctx.ignore_file {
+            let path = fs::canonicalize(ignore_file)
+                .map_err(|e| Error::IgnoreFile(format!("{}: {e}", ignore_file.display())))?;
+
+            if let Some(e) = builder.add_ignore(&path) {
+                return Err(Error::IgnoreFile(format!("{}: {e}", path.display())));
+            }
+        }
+
         if ctx.suppress_size && ctx.level() == 1 {
             builder.max_depth(Some(1)).threads(1);
         }
 
-        if ctx.pattern.is_some() {
-            if ctx.glob || ctx.iglob {
-                builder.filter_entry(ctx.glob_predicate()?);
-            } else {
-                builder.filter_entry(ctx.regex_predicate()?);
-            }
+        let predicate = walk_predicate(ctx)?;
+
+        // Entries that pass the predicate are queued, which is counted to tell which directories
+        // weren't read in full if traversal is cut short.
+        if cutoff::is_possible(ctx) {
+            builder.filter_entry(move |dir_entry| {
+                let keep = predicate
+                    .as_ref()
+                    .map_or(true, |predicate| predicate(dir_entry));
+
+                if keep {
+                    visitor::count_queued();
+                }
+
+                keep
+            });
+        } else if let Some(predicate) = predicate {
+            builder.filter_entry(predicate);
         }
 
         Ok(builder.build_parallel())
     }
 }
+
+/// Determines whether or not an entry should be traversed.
+type Predicate = Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static>;
+
+/// The predicate used to filter entries when a `--pattern` or any of the metadata filters are
+/// provided. Entries must satisfy both if both are provided.
+fn walk_predicate(ctx: &Context) -> StdResult<Option<Predicate>, Error> {
+    let pattern_predicate = if ctx.pattern.is_none() {
+        None
+    } else if ctx.glob || ctx.iglob {
+        Some(ctx.glob_predicate()?)
+    } else {
+        Some(ctx.regex_predicate()?)
+    };
+
+    #[cfg(unix)]
+    let metadata_predicate = if ctx.filters_metadata() {
+        Some(ctx.metadata_predicate()?)
+    } else {
+        None
+    };
+
+    #[cfg(not(unix))]
+    let metadata_predicate: Option<Predicate> = None;
+
+    let predicate: Predicate = match (pattern_predicate, metadata_predicate) {
+        (Some(pattern), Some(metadata)) => {
+            Box::new(move |dir_entry| pattern(dir_entry) && metadata(dir_entry))
+        },
+        (Some(predicate), None) | (None, Some(predicate)) => predicate,
+        (None, None) => return Ok(None),
+    };
+
+    Ok(Some(predicate))
+}
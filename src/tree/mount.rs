@@ -0,0 +1,134 @@
+use super::Node;
+use crate::{context::Context, disk_usage::file_size::FileSize, fs::mount::Mount};
+use std::{
+    fmt::{self, Display},
+    ptr,
+};
+
+/// Per-filesystem totals of a [`Tree`] whose traversal crossed onto filesystems other than that
+/// of the root.
+///
+/// [`Tree`]: super::Tree
+#[derive(Default)]
+pub struct Summary {
+    entries: Vec<Entry>,
+}
+
+/// Totals for a single [Mount].
+struct Entry {
+    mount: &'static Mount,
+    files: u64,
+    size: Option<FileSize>,
+}
+
+impl Summary {
+    /// Accounts for a single [Node] residing on `mount`. Only files count towards the totals as
+    /// the sizes of directories are aggregates of their contents.
+    pub fn add(&mut self, mount: &'static Mount, node: &Node, ctx: &Context) {
+        let index = self.index_of(mount, ctx);
+
+        if node.is_dir() {
+            return;
+        }
+
+        let entry = &mut self.entries[index];
+        entry.files += 1;
+
+        if let (Some(total), Some(file_size)) = (entry.size.as_mut(), node.file_size()) {
+            *total += file_size;
+        }
+    }
+
+    /// Adds the totals of each filesystem in `other` to those of [Self].
+    pub fn merge(&mut self, other: &Self, ctx: &Context) {
+        for other_entry in &other.entries {
+            let index = self.index_of(other_entry.mount, ctx);
+            let entry = &mut self.entries[index];
+            entry.files += other_entry.files;
+
+            if let (Some(total), Some(file_size)) = (entry.size.as_mut(), &other_entry.size) {
+                *total += file_size;
+            }
+        }
+    }
+
+    /// Index of the [Entry] for `mount`, which is added if there isn't one yet.
+    fn index_of(&mut self, mount: &'static Mount, ctx: &Context) -> usize {
+        if let Some(i) = self.entries.iter().position(|e| ptr::eq(e.mount, mount)) {
+            return i;
+        }
+
+        self.entries.push(Entry {
+            mount,
+            files: 0,
+            size: (!ctx.suppress_size).then(|| FileSize::from(ctx)),
+        });
+
+        self.entries.len() - 1
+    }
+}
+
+impl Display for Summary {
+    /// Renders a table with a row per filesystem sorted by mount point.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = self
+            .entries
+            .iter()
+            .map(|entry| {
+                [
+                    entry.mount.path.display().to_string(),
+                    entry.mount.fs_type.clone(),
+                    entry.mount.source.clone(),
+                    entry.files.to_string(),
+                    entry
+                        .size
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        rows.sort_by(|a, b| a[0].cmp(&b[0]));
+
+        let headers = ["Filesystem", "Type", "Source", "Files", "Size"];
+
+        let columns = if self.entries.iter().any(|e| e.size.is_some()) {
+            headers.len()
+        } else {
+            headers.len() - 1
+        };
+
+        let widths = (0..columns)
+            .map(|i| {
+                rows.iter()
+                    .map(|row| row[i].chars().count())
+                    .fold(headers[i].len(), usize::max)
+            })
+            .collect::<Vec<_>>();
+
+        let fmt_row = |f: &mut fmt::Formatter<'_>, cells: &[&str]| {
+            for (i, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+                let sep = if i == 0 { "" } else { "  " };
+
+                // Counts and sizes are right-aligned while everything else is left-aligned.
+                if i < 3 {
+                    write!(f, "{sep}{cell:<width$}")?;
+                } else {
+                    write!(f, "{sep}{cell:>width$}")?;
+                }
+            }
+            Ok(())
+        };
+
+        fmt_row(f, &headers[..columns])?;
+
+        for row in &rows {
+            writeln!(f)?;
+            let cells = row.iter().map(String::as_str).collect::<Vec<_>>();
+            fmt_row(f, &cells[..columns])?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
 use crate::{
     context::Context,
-    disk_usage::file_size::{byte, line_count, word_count, DiskUsage, FileSize},
-    fs::inode::Inode,
+    disk_usage::file_size::{DiskUsage, FileSize, Text},
+    fs::{
+        inode::Inode,
+        kind::Kind,
+        link::Chain,
+        mount::{self, MountPoint},
+    },
     icons,
     styles::get_ls_colors,
     tree::error::Error,
@@ -9,6 +14,7 @@ use crate::{
 use ansi_term::Style;
 use ignore::DirEntry;
 use lscolors::Style as LS_Style;
+use stat::Stat;
 use std::{
     borrow::Cow,
     convert::TryFrom,
@@ -20,13 +26,23 @@ use std::{
 
 #[cfg(unix)]
 use crate::{
-    disk_usage::file_size::block,
-    fs::permissions::{FileMode, SymbolicNotation},
+    context::time,
+    fs::{
+        permissions::{audit::Findings, FileMode, SymbolicNotation},
+        xattr::Xattr,
+    },
 };
 
 /// Ordering and sorting rules for [Node].
 pub mod cmp;
 
+/// Initialization of [Node] from the entries of the getdents scanner.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+mod scan;
+
+/// The subset of file metadata retained by each [Node].
+pub mod stat;
+
 /// File attributes specific to Unix systems.
 #[cfg(unix)]
 pub mod unix;
@@ -35,14 +51,29 @@ pub mod unix;
 /// relevant system calls are expected to complete after initialization. A `Node` when `Display`ed
 /// uses ANSI colors determined by the file-type and `LS_COLORS`.
 ///
+/// Only the file name is kept rather than the full path, which can be reassembled from the names
+/// of the node's ancestors in the [`Tree`], and only the metadata that is rendered.
+///
 /// [`Tree`]: super::Tree
 pub struct Node {
-    dir_entry: DirEntry,
-    metadata: Metadata,
+    name: Box<OsStr>,
+    depth: usize,
+    stat: Stat,
     file_size: Option<FileSize>,
+    secondary_file_sizes: Box<[FileSize]>,
     style: Option<Style>,
-    symlink_target: Option<PathBuf>,
+    symlink_target: Option<Box<Path>>,
+    link_chain: Option<Box<Chain>>,
     inode: Option<Inode>,
+    kind: Option<Kind>,
+    mount_point: Option<MountPoint>,
+
+    /// Whether traversal was cut short before everything beneath the directory was read.
+    incomplete: bool,
+
+    /// Whether the directory itself satisfies the metadata filters, in which case it's kept even
+    /// if nothing beneath it does.
+    matched: bool,
 
     #[cfg(unix)]
     unix_attrs: unix::Attrs,
@@ -50,22 +81,35 @@ pub struct Node {
 
 impl Node {
     /// Initializes a new [Node].
+    #[allow(clippy::too_many_arguments)]
     pub const fn new(
-        dir_entry: DirEntry,
-        metadata: Metadata,
+        name: Box<OsStr>,
+        depth: usize,
+        stat: Stat,
         file_size: Option<FileSize>,
+        secondary_file_sizes: Box<[FileSize]>,
         style: Option<Style>,
-        symlink_target: Option<PathBuf>,
+        symlink_target: Option<Box<Path>>,
+        link_chain: Option<Box<Chain>>,
         inode: Option<Inode>,
+        kind: Option<Kind>,
+        mount_point: Option<MountPoint>,
         #[cfg(unix)] unix_attrs: unix::Attrs,
     ) -> Self {
         Self {
-            dir_entry,
-            metadata,
+            name,
+            depth,
+            stat,
             file_size,
+            secondary_file_sizes,
             style,
             symlink_target,
+            link_chain,
             inode,
+            kind,
+            mount_point,
+            incomplete: false,
+            matched: false,
             #[cfg(unix)]
             unix_attrs,
         }
@@ -73,43 +117,59 @@ impl Node {
 
     /// Returns a reference to `file_name`. If file is a symlink then `file_name` is the name of
     /// the symlink not the target.
-    pub fn file_name(&self) -> &OsStr {
-        self.dir_entry.file_name()
-    }
-
-    pub const fn dir_entry(&self) -> &DirEntry {
-        &self.dir_entry
+    pub const fn file_name(&self) -> &OsStr {
+        &self.name
     }
 
     /// Get depth level of [Node].
-    pub fn depth(&self) -> usize {
-        self.dir_entry.depth()
+    pub const fn depth(&self) -> usize {
+        self.depth
     }
 
     /// Gets the number of blocks used by the underlying [`DirEntry`]. Returns `None` in the case of
     /// no blocks allocated like in the case of directories.
     #[cfg(unix)]
-    pub fn blocks(&self) -> Option<u64> {
-        use std::os::unix::fs::MetadataExt;
-
-        let blocks = self.metadata.blocks();
+    pub const fn blocks(&self) -> Option<u64> {
+        let blocks = self.stat.blocks();
 
-        (blocks != 0).then_some(blocks)
+        if blocks == 0 {
+            None
+        } else {
+            Some(blocks)
+        }
     }
 
-    /// Timestamp of when file was last modified.
+    /// Timestamp of when file was last modified. Timestamps are only retained if they're
+    /// displayed or sorted by.
     pub fn modified(&self) -> Option<SystemTime> {
-        self.metadata.modified().ok()
+        self.stat.modified()
     }
 
     /// Timestamp of when file was created.
     pub fn created(&self) -> Option<SystemTime> {
-        self.metadata.created().ok()
+        self.stat.created()
     }
 
     /// Timestamp of when file was last accessed.
     pub fn accessed(&self) -> Option<SystemTime> {
-        self.metadata.accessed().ok()
+        self.stat.accessed()
+    }
+
+    /// Timestamp of when the file's status, i.e. its inode, was last changed.
+    #[cfg(unix)]
+    pub fn changed(&self) -> Option<SystemTime> {
+        self.stat.changed()
+    }
+
+    /// The timestamp of the given kind.
+    #[cfg(unix)]
+    pub fn timestamp(&self, stamp: time::Stamp) -> Option<SystemTime> {
+        match stamp {
+            time::Stamp::Create => self.created(),
+            time::Stamp::Change => self.changed(),
+            time::Stamp::Access => self.accessed(),
+            time::Stamp::Mod => self.modified(),
+        }
     }
 
     /// Gets the underlying [Inode] of the entry.
@@ -131,7 +191,28 @@ impl Node {
 
     /// Returns `true` if node is a directory.
     pub fn is_dir(&self) -> bool {
-        self.file_type().map_or(false, |ft| ft.is_dir())
+        self.kind == Some(Kind::Dir)
+    }
+
+    /// Returns `true` if traversal was cut short before everything beneath the directory was read,
+    /// in which case its size is a lower bound.
+    pub const fn is_incomplete(&self) -> bool {
+        self.incomplete
+    }
+
+    /// Marks the directory as not having been read in full.
+    pub fn mark_incomplete(&mut self) {
+        self.incomplete = true;
+    }
+
+    /// Returns `true` if the directory itself satisfies the metadata filters.
+    pub const fn is_matched(&self) -> bool {
+        self.matched
+    }
+
+    /// Marks the directory as satisfying the metadata filters.
+    pub fn mark_matched(&mut self) {
+        self.matched = true;
     }
 
     /// Is the Node a symlink.
@@ -139,6 +220,21 @@ impl Node {
         self.symlink_target.is_some()
     }
 
+    /// Is the Node a symlink whose target doesn't exist.
+    pub fn is_broken_link(&self) -> bool {
+        self.kind == Some(Kind::BrokenLink)
+    }
+
+    /// Returns the [Kind] of the [Node] if it's anything other than a regular, non-executable file.
+    pub const fn kind(&self) -> Option<Kind> {
+        self.kind
+    }
+
+    /// Returns the [`MountPoint`] if the [Node] is a directory on which a filesystem is mounted.
+    pub const fn mount_point(&self) -> Option<MountPoint> {
+        self.mount_point
+    }
+
     /// Path to symlink target.
     pub fn symlink_target_path(&self) -> Option<&Path> {
         self.symlink_target.as_deref()
@@ -149,20 +245,17 @@ impl Node {
         self.symlink_target_path().map(Path::as_os_str)
     }
 
-    /// Returns reference to underlying [`FileType`].
-    pub fn file_type(&self) -> Option<FileType> {
-        self.dir_entry.file_type()
-    }
-
-    /// Returns the path to the [Node]'s parent, if any.
-    pub fn parent_path(&self) -> Option<&Path> {
-        self.path().parent()
+    /// The full resolution [Chain] of the symlink if it was computed.
+    pub fn link_chain(&self) -> Option<&Chain> {
+        self.link_chain.as_deref()
     }
 
-    /// Returns a reference to `path`. If the underlying [`DirEntry`] is a symlink then the path of
-    /// the symlink shall be returned.
-    pub fn path(&self) -> &Path {
-        self.dir_entry.path()
+    /// Resolves the full [Chain] of the symlink at `path` if it hasn't been already. `root` is
+    /// expected to be the canonical path of the root of the traversal.
+    pub fn resolve_link_chain(&mut self, path: &Path, root: &Path) {
+        if self.link_chain.is_none() && self.is_symlink() {
+            self.link_chain = Some(Box::new(Chain::resolve(path, root)));
+        }
     }
 
     /// Gets '`file_size`'.
@@ -175,11 +268,84 @@ impl Node {
         self.file_size = Some(size);
     }
 
+    /// Gets the sizes of the disk usage metrics other than the primary one.
+    pub fn secondary_file_sizes(&self) -> &[FileSize] {
+        &self.secondary_file_sizes
+    }
+
+    /// Sets `secondary_file_sizes`.
+    pub fn set_secondary_file_sizes(&mut self, sizes: Vec<FileSize>) {
+        self.secondary_file_sizes = sizes.into_boxed_slice();
+    }
+
+    /// Removes and returns the sizes of the [Node]. For a directory these are the sizes of the
+    /// descendants folded into it by [`Node::add_sizes_of`].
+    pub fn take_file_sizes(&mut self) -> (Option<FileSize>, Box<[FileSize]>) {
+        (
+            self.file_size.take(),
+            std::mem::take(&mut self.secondary_file_sizes),
+        )
+    }
+
+    /// Adds the sizes of `descendant` to those of the [Node], which is expected to be a directory
+    /// into which descendants beyond the maximum depth of the output are folded.
+    pub fn add_sizes_of(&mut self, descendant: &Self, ctx: &Context) {
+        if let Some(file_size) = descendant.file_size() {
+            *self.file_size.get_or_insert_with(|| FileSize::from(ctx)) += file_size;
+        }
+
+        for file_size in descendant.secondary_file_sizes() {
+            let disk_usage = file_size.disk_usage();
+
+            if let Some(total) = self
+                .secondary_file_sizes
+                .iter_mut()
+                .find(|total| total.disk_usage() == disk_usage)
+            {
+                *total += file_size;
+                continue;
+            }
+
+            let mut total = FileSize::init_empty(disk_usage, ctx);
+            total += file_size;
+
+            let mut totals = std::mem::take(&mut self.secondary_file_sizes).into_vec();
+            totals.push(total);
+            self.secondary_file_sizes = totals.into_boxed_slice();
+        }
+    }
+
+    /// Gets the [`FileSize`] of the provided disk usage metric if it was computed.
+    pub fn file_size_of(&self, disk_usage: DiskUsage) -> Option<&FileSize> {
+        self.file_size()
+            .filter(|file_size| file_size.disk_usage() == disk_usage)
+            .or_else(|| {
+                self.secondary_file_sizes
+                    .iter()
+                    .find(|file_size| file_size.disk_usage() == disk_usage)
+            })
+    }
+
+    /// Physical size as a percentage of logical size. Anything below 100 indicates a sparse or a
+    /// filesystem-compressed file. Returns `None` if either of the sizes weren't computed or if
+    /// the logical size is zero.
+    pub fn size_ratio(&self) -> Option<u64> {
+        let logical = self.file_size_of(DiskUsage::Logical)?.value();
+        let physical = self.file_size_of(DiskUsage::Physical)?.value();
+
+        if logical == 0 {
+            return None;
+        }
+
+        let ratio = u128::from(physical) * 100 / u128::from(logical);
+
+        u64::try_from(ratio).ok()
+    }
+
     /// Attempts to return an instance of [`FileMode`] for the display of symbolic permissions.
     #[cfg(unix)]
     pub fn mode(&self) -> Result<FileMode, Error> {
-        let permissions = self.metadata.permissions();
-        let file_mode = permissions.try_mode_symbolic_notation()?;
+        let file_mode = FileMode::try_from(self.stat.mode())?;
         Ok(file_mode)
     }
 
@@ -189,6 +355,18 @@ impl Node {
         self.unix_attrs.has_xattrs
     }
 
+    /// What about the file warrants attention during a security review, if it was audited.
+    #[cfg(unix)]
+    pub const fn findings(&self) -> Findings {
+        self.unix_attrs.findings
+    }
+
+    /// The extended attributes of the file if they were queried.
+    #[cfg(unix)]
+    pub fn xattrs(&self) -> &[Xattr] {
+        &self.unix_attrs.xattrs
+    }
+
     /// Returns the owner of the [`Node`].
     #[cfg(unix)]
     pub fn owner(&self) -> Option<&str> {
@@ -209,22 +387,39 @@ impl Node {
     /// See [`crate::icons::fs::compute`].
     pub fn compute_icon(&self, no_color: bool) -> Cow<'static, str> {
         if no_color {
-            icons::fs::compute(self.dir_entry(), self.symlink_target_path())
+            icons::fs::compute(self.file_name(), self.kind, self.symlink_target_path())
         } else {
-            icons::fs::compute_with_color(self.dir_entry(), self.symlink_target_path(), self.style)
+            icons::fs::compute_with_color(
+                self.file_name(),
+                self.kind,
+                self.symlink_target_path(),
+                self.style,
+            )
         }
     }
 }
 
-impl TryFrom<(DirEntry, &Context)> for Node {
+impl TryFrom<(&DirEntry, &Context)> for Node {
     type Error = Error;
 
-    fn try_from(data: (DirEntry, &Context)) -> Result<Self, Error> {
+    fn try_from(data: (&DirEntry, &Context)) -> Result<Self, Error> {
         let (dir_entry, ctx) = data;
 
+        Self::try_from((dir_entry, dir_entry.depth(), ctx))
+    }
+}
+
+/// Initializes a [Node] from a [`DirEntry`] that sits at the given depth of the traversal, which
+/// may differ from [`DirEntry::depth`] for entries that weren't produced by the traversal itself.
+impl TryFrom<(&DirEntry, usize, &Context)> for Node {
+    type Error = Error;
+
+    fn try_from(data: (&DirEntry, usize, &Context)) -> Result<Self, Error> {
+        let (dir_entry, depth, ctx) = data;
+
         let path = dir_entry.path();
 
-        let link_target = crate::fs::symlink_target(&dir_entry);
+        let link_target = crate::fs::symlink_target(dir_entry).map(PathBuf::into_boxed_path);
 
         let metadata = dir_entry.metadata()?;
 
@@ -236,59 +431,103 @@ impl TryFrom<(DirEntry, &Context)> for Node {
 
         let file_type = dir_entry.file_type();
 
-        let file_size = match file_type {
-            Some(ref ft)
-                if !ctx.suppress_size && (ft.is_file() || ft.is_symlink() && !ctx.follow) =>
-            {
-                match ctx.disk_usage {
-                    DiskUsage::Logical => {
-                        let metric = byte::Metric::init_logical(&metadata, ctx.unit, ctx.human);
-                        Some(FileSize::Byte(metric))
-                    },
-                    DiskUsage::Physical => {
-                        let metric =
-                            byte::Metric::init_physical(path, &metadata, ctx.unit, ctx.human);
-                        Some(FileSize::Byte(metric))
-                    },
-                    DiskUsage::Line => {
-                        let metric = line_count::Metric::init(path);
-                        metric.map(FileSize::Line)
-                    },
-                    DiskUsage::Word => {
-                        let metric = word_count::Metric::init(path);
-                        metric.map(FileSize::Word)
-                    },
-
-                    #[cfg(unix)]
-                    DiskUsage::Block => {
-                        let metric = block::Metric::init(&metadata);
-                        Some(FileSize::Block(metric))
-                    },
-                }
+        let has_size =
+            |ft: &FileType| !ctx.suppress_size && (ft.is_file() || ft.is_symlink() && !ctx.follow);
+
+        let (file_size, secondary_file_sizes) = match file_type {
+            Some(ref ft) if has_size(ft) => {
+                let mut text = Text::new(path, ctx);
+
+                let file_size =
+                    FileSize::try_init(ctx.disk_usage(), path, &metadata, &mut text, ctx);
+
+                let secondary_file_sizes = ctx
+                    .secondary_disk_usages()
+                    .iter()
+                    .filter_map(|&disk_usage| {
+                        FileSize::try_init(disk_usage, path, &metadata, &mut text, ctx)
+                    })
+                    .collect();
+
+                (file_size, secondary_file_sizes)
             },
-            _ => None,
+            _ => (None, Box::default()),
         };
 
         let inode = Inode::try_from(&metadata).ok();
 
-        #[cfg(unix)]
-        let unix_attrs = if ctx.long
-            && path.components().count() <= ctx.dir_canonical().components().count().saturating_add(ctx.level())
-        {
-            unix::Attrs::from((&metadata, &dir_entry))
+        let kind = Kind::of(dir_entry, &metadata);
+
+        let link_chain = (ctx.link_chain && link_target.is_some())
+            .then(|| Box::new(Chain::resolve(path, ctx.dir_canonical())));
+
+        let mount_point = if metadata.is_dir() {
+            mount::lookup(path).map(|mount| MountPoint {
+                mount,
+                crossed: !ctx.same_fs || depth == 0 || same_device(&metadata, ctx),
+            })
         } else {
-            unix::Attrs::default()
+            None
+        };
+
+        let displayed = depth <= ctx.level();
+
+        let stat = Stat::new(
+            &metadata,
+            ctx.sort.is_by_time() || (ctx.long_view() && displayed),
+        );
+
+        #[cfg(unix)]
+        let unix_attrs = {
+            use crate::fs::xattr::ExtendedAttr;
+
+            let mut unix_attrs = if ctx.long_view() && displayed {
+                unix::Attrs::from((&metadata, dir_entry, ctx))
+            } else {
+                unix::Attrs::default()
+            };
+
+            if ctx.xattrs && displayed {
+                unix_attrs.xattrs = dir_entry.xattrs(ctx.xattr_values).into_boxed_slice();
+            }
+
+            if ctx.audit {
+                if let Ok(mode) = metadata.permissions().try_mode_symbolic_notation() {
+                    let has_capabilities = metadata.is_file() && dir_entry.has_capabilities();
+                    unix_attrs.findings = Findings::of(&mode, has_capabilities);
+                }
+            }
+
+            unix_attrs
         };
 
         Ok(Self::new(
-            dir_entry,
-            metadata,
+            Box::from(dir_entry.file_name()),
+            depth,
+            stat,
             file_size,
+            secondary_file_sizes,
             style,
             link_target,
+            link_chain,
             inode,
+            kind,
+            mount_point,
             #[cfg(unix)]
             unix_attrs,
         ))
     }
 }
+
+/// Whether or not the file described by `metadata` is on the same device as the root directory.
+#[cfg(unix)]
+fn same_device(metadata: &Metadata, ctx: &Context) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::metadata(ctx.dir()).is_ok_and(|root| root.dev() == metadata.dev())
+}
+
+#[cfg(not(unix))]
+const fn same_device(_metadata: &Metadata, _ctx: &Context) -> bool {
+    true
+}
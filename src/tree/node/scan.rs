@@ -0,0 +1,124 @@
+use super::{stat::Stat, unix, Node};
+use crate::{
+    context::Context,
+    disk_usage::file_size::{FileSize, Text},
+    fs::{
+        inode::Inode,
+        kind::Kind,
+        link::Chain,
+        mount::{self, MountPoint},
+        permissions::{audit::Findings, FileMode},
+        statx::Statx,
+        ug,
+        xattr::ExtendedAttr,
+    },
+    styles::{get_ls_colors, indicator},
+    tree::scan::Entry,
+};
+use ansi_term::Style;
+use lscolors::Style as LS_Style;
+use std::{fs, os::unix::fs::MetadataExt, path::PathBuf};
+
+/// Initializes a [Node] from an [Entry] of the getdents scanner, which is the counterpart of
+/// initializing it from a [`ignore::DirEntry`] with the status of the file coming from `statx(2)`
+/// rather than from its [`std::fs::Metadata`].
+impl From<(&Entry<'_>, &Context)> for Node {
+    fn from((entry, ctx): (&Entry<'_>, &Context)) -> Self {
+        let path = entry.path();
+        let statx = entry.statx();
+        let mode = statx.mode();
+
+        let link_target = statx
+            .is_symlink()
+            .then(|| fs::read_link(path).ok())
+            .flatten()
+            .map(PathBuf::into_boxed_path);
+
+        let kind = Kind::of_mode(path, mode);
+
+        let style = get_ls_colors().ok().map(|ls_colors| {
+            let broken = kind == Some(Kind::BrokenLink);
+
+            indicator::style_for_mode(ls_colors, path, mode, statx.nlink(), broken)
+                .map_or_else(Style::default, LS_Style::to_ansi_term_style)
+        });
+
+        let (file_size, secondary_file_sizes) =
+            if !ctx.suppress_size && (statx.is_file() || statx.is_symlink()) {
+                let mut text = Text::new(path, ctx);
+
+                let file_size = FileSize::try_init_statx(ctx.disk_usage(), statx, &mut text, ctx);
+
+                let secondary_file_sizes = ctx
+                    .secondary_disk_usages()
+                    .iter()
+                    .filter_map(|&disk_usage| {
+                        FileSize::try_init_statx(disk_usage, statx, &mut text, ctx)
+                    })
+                    .collect();
+
+                (file_size, secondary_file_sizes)
+            } else {
+                (None, Box::default())
+            };
+
+        let inode = Inode::new(statx.ino(), statx.dev(), statx.nlink());
+
+        let link_chain = (ctx.link_chain && link_target.is_some())
+            .then(|| Box::new(Chain::resolve(path, ctx.dir_canonical())));
+
+        let mount_point = if statx.is_dir() {
+            mount::lookup(path).map(|mount| MountPoint {
+                mount,
+                crossed: !ctx.same_fs || entry.depth() == 0 || same_device(statx, ctx),
+            })
+        } else {
+            None
+        };
+
+        let displayed = entry.depth() <= ctx.level();
+
+        let stat = Stat::from_statx(
+            statx,
+            ctx.sort.is_by_time() || (ctx.long_view() && displayed),
+        );
+
+        let mut unix_attrs = if ctx.long_view() && displayed {
+            let (owner, group) = ug::owner_and_group(statx.uid(), statx.gid(), ctx.numeric_ids);
+            unix::Attrs::new(entry.has_xattrs(), Some(owner), Some(group))
+        } else {
+            unix::Attrs::default()
+        };
+
+        if ctx.xattrs && displayed {
+            unix_attrs.xattrs = entry.xattrs(ctx.xattr_values).into_boxed_slice();
+        }
+
+        if ctx.audit {
+            if let Ok(mode) = FileMode::try_from(mode) {
+                let has_capabilities = statx.is_file() && entry.has_capabilities();
+                unix_attrs.findings = Findings::of(&mode, has_capabilities);
+            }
+        }
+
+        Self::new(
+            Box::from(entry.file_name()),
+            entry.depth(),
+            stat,
+            file_size,
+            secondary_file_sizes,
+            style,
+            link_target,
+            link_chain,
+            Some(inode),
+            kind,
+            mount_point,
+            unix_attrs,
+        )
+    }
+}
+
+/// Whether or not the file described by `statx` is on the same device as the root directory.
+fn same_device(statx: &Statx, ctx: &Context) -> bool {
+    fs::metadata(ctx.dir()).is_ok_and(|root| root.dev() == statx.dev())
+}
@@ -0,0 +1,130 @@
+use std::{fs::Metadata, time::SystemTime};
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use crate::fs::statx::Statx;
+
+/// The fields of a file's [`Metadata`] that are rendered or sorted by. Everything else is dropped
+/// once the [`Node`] is initialized so as to keep the footprint of very large trees small.
+///
+/// [`Node`]: super::Node
+pub struct Stat {
+    /// The raw `st_mode` from which the file's permissions are derived.
+    #[cfg(unix)]
+    mode: u32,
+
+    /// Number of 512-byte blocks allocated to the file.
+    #[cfg(unix)]
+    blocks: u64,
+
+    /// Only retained if the timestamps are displayed or sorted by.
+    times: Option<Box<Times>>,
+}
+
+/// The timestamps of a file.
+struct Times {
+    modified: Option<SystemTime>,
+    created: Option<SystemTime>,
+    accessed: Option<SystemTime>,
+
+    #[cfg(unix)]
+    changed: Option<SystemTime>,
+}
+
+impl Stat {
+    /// Retains what's needed of `metadata`, leaving out the timestamps unless `with_times` is
+    /// `true`.
+    pub fn new(metadata: &Metadata, with_times: bool) -> Self {
+        #[cfg(unix)]
+        use std::os::unix::fs::MetadataExt;
+
+        let times = with_times.then(|| {
+            Box::new(Times {
+                modified: metadata.modified().ok(),
+                created: metadata.created().ok(),
+                accessed: metadata.accessed().ok(),
+                #[cfg(unix)]
+                changed: changed(metadata),
+            })
+        });
+
+        Self {
+            #[cfg(unix)]
+            mode: metadata.mode(),
+            #[cfg(unix)]
+            blocks: metadata.blocks(),
+            times,
+        }
+    }
+
+    /// Retains what's needed of the status queried by `statx(2)`, leaving out the timestamps
+    /// unless `with_times` is `true`.
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    pub fn from_statx(statx: &Statx, with_times: bool) -> Self {
+        let times = with_times.then(|| {
+            Box::new(Times {
+                modified: statx.modified(),
+                created: statx.created(),
+                accessed: statx.accessed(),
+                changed: statx.changed(),
+            })
+        });
+
+        Self {
+            mode: statx.mode(),
+            blocks: statx.blocks(),
+            times,
+        }
+    }
+
+    /// The raw `st_mode` of the file.
+    #[cfg(unix)]
+    pub const fn mode(&self) -> u32 {
+        self.mode
+    }
+
+    /// Number of 512-byte blocks allocated to the file.
+    #[cfg(unix)]
+    pub const fn blocks(&self) -> u64 {
+        self.blocks
+    }
+
+    /// Timestamp of when file was last modified.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.times.as_ref()?.modified
+    }
+
+    /// Timestamp of when file was created.
+    pub fn created(&self) -> Option<SystemTime> {
+        self.times.as_ref()?.created
+    }
+
+    /// Timestamp of when file was last accessed.
+    pub fn accessed(&self) -> Option<SystemTime> {
+        self.times.as_ref()?.accessed
+    }
+
+    /// Timestamp of when the file's status, i.e. its inode, was last changed.
+    #[cfg(unix)]
+    pub fn changed(&self) -> Option<SystemTime> {
+        self.times.as_ref()?.changed
+    }
+}
+
+/// Converts the `ctime` of `metadata` into a [`SystemTime`].
+#[cfg(unix)]
+fn changed(metadata: &Metadata) -> Option<SystemTime> {
+    use std::{
+        os::unix::fs::MetadataExt,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    let secs = metadata.ctime();
+    let nanos = u32::try_from(metadata.ctime_nsec()).ok()?;
+
+    match u64::try_from(secs) {
+        Ok(secs) => UNIX_EPOCH.checked_add(Duration::new(secs, nanos)),
+        Err(_) => UNIX_EPOCH
+            .checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+            .checked_add(Duration::from_nanos(u64::from(nanos))),
+    }
+}
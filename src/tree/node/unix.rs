@@ -1,22 +1,37 @@
-use crate::fs::{ug::UserGroupInfo, xattr::ExtendedAttr};
+use crate::{
+    context::Context,
+    fs::{
+        permissions::audit::Findings,
+        ug::UserGroupInfo,
+        xattr::{ExtendedAttr, Xattr},
+    },
+};
 use ignore::DirEntry;
-use std::{convert::From, fs::Metadata};
+use std::{convert::From, fs::Metadata, sync::Arc};
 
 /// File attributes that are optionally computed and specific to Unix-like systems.
 #[derive(Default)]
 pub struct Attrs {
     pub has_xattrs: bool,
-    owner: Option<String>,
-    group: Option<String>,
+    owner: Option<Arc<str>>,
+    group: Option<Arc<str>>,
+
+    /// The extended attributes themselves, which are only queried when they're to be listed.
+    pub xattrs: Box<[Xattr]>,
+
+    /// What about the file warrants attention during a security review, if it was audited.
+    pub findings: Findings,
 }
 
 impl Attrs {
     /// Constructor for [`Attrs`].
-    pub const fn new(has_xattrs: bool, owner: Option<String>, group: Option<String>) -> Self {
+    pub fn new(has_xattrs: bool, owner: Option<Arc<str>>, group: Option<Arc<str>>) -> Self {
         Self {
             has_xattrs,
             owner,
             group,
+            xattrs: Box::default(),
+            findings: Findings::empty(),
         }
     }
 
@@ -31,15 +46,14 @@ impl Attrs {
     }
 }
 
-/// Initializes a [`Attrs`] from a [`DirEntry`].
-impl From<(&Metadata, &DirEntry)> for Attrs {
-    fn from((md, entry): (&Metadata, &DirEntry)) -> Self {
+/// Initializes a [`Attrs`] from a [`DirEntry`]. Owners and groups are shown as numeric ids if
+/// requested or if they no longer exist.
+impl From<(&Metadata, &DirEntry, &Context)> for Attrs {
+    fn from((md, entry, ctx): (&Metadata, &DirEntry, &Context)) -> Self {
         let has_xattrs = entry.has_xattrs();
 
-        if let Ok((o, g)) = md.try_get_owner_and_group() {
-            return Self::new(has_xattrs, Some(o), Some(g));
-        }
+        let (owner, group) = md.owner_and_group(ctx.numeric_ids);
 
-        Self::new(has_xattrs, None, None)
+        Self::new(has_xattrs, Some(owner), Some(group))
     }
 }
@@ -0,0 +1,404 @@
+use super::{cutoff, node::Node, visitor::TraversalState};
+use crate::{
+    context::Context,
+    disk_usage::file_size::DiskUsage,
+    fs::{
+        getdents::Dir,
+        statx::{self, Statx},
+        xattr::ExtendedAttr,
+    },
+};
+use rules::Rules;
+use std::{
+    ffi::{CString, OsStr},
+    fs,
+    os::{
+        fd::AsFd,
+        unix::{ffi::OsStrExt, fs::MetadataExt},
+    },
+    path::{Path, PathBuf},
+    sync::{mpsc::Sender, Condvar, Mutex},
+    thread,
+};
+
+/// The rules by which entries are ignored.
+mod rules;
+
+/// A parallel traversal that reads directories with `getdents64(2)` and only queries the parts of
+/// the status of files that are displayed or needed to compute disk usage with `statx(2)`. Entries
+/// are ignored the same way as they are by the [`ignore::WalkParallel`] of the portable traversal,
+/// which is used instead where [`Context::scans_with_getdents`] isn't satisfied.
+pub struct Scanner<'a> {
+    ctx: &'a Context,
+    root: PathBuf,
+    root_statx: Statx,
+    rules: Rules,
+
+    /// The device of the root directory if traversal is confined to its filesystem.
+    root_dev: Option<u64>,
+
+    max_depth: Option<usize>,
+    threads: usize,
+
+    /// What's queried of entries that are beyond the maximum depth of the output.
+    hidden_mask: u32,
+
+    /// What's queried of entries that are displayed.
+    displayed_mask: u32,
+}
+
+/// A file found by the [Scanner] along with its status.
+pub struct Entry<'a> {
+    path: &'a Path,
+    depth: usize,
+    statx: &'a Statx,
+}
+
+/// A directory that has yet to be read along with the rules of its parent directory.
+struct Work {
+    path: PathBuf,
+    depth: usize,
+    rules: Rules,
+}
+
+/// The directories that have yet to be read, which are shared by all threads of the [Scanner].
+/// Directories are read depth-first to keep the amount of pending work small.
+#[derive(Default)]
+struct Queue {
+    state: Mutex<State>,
+    available: Condvar,
+}
+
+/// See [Queue].
+#[derive(Default)]
+struct State {
+    work: Vec<Work>,
+
+    /// Directories that were queued but not yet fully read.
+    pending: usize,
+
+    /// Set if traversal was cut short.
+    quit: bool,
+}
+
+impl<'a> Scanner<'a> {
+    /// Returns `None` if the [Context] calls for the portable traversal or if `statx(2)` isn't
+    /// available, in which case the portable traversal should be used instead.
+    pub fn new(ctx: &'a Context) -> Option<Self> {
+        if !ctx.scans_with_getdents() {
+            return None;
+        }
+
+        let root = ctx.dir_canonical().to_path_buf();
+        let displayed_mask = mask(ctx, true);
+
+        let root_path = CString::new(root.as_os_str().as_bytes()).ok()?;
+        let root_statx = Statx::at(None, &root_path, displayed_mask).ok()?;
+
+        let root_dev = if ctx.same_fs {
+            Some(fs::metadata(&root).ok()?.dev())
+        } else {
+            None
+        };
+
+        let rules = Rules::new(ctx).ok()?.add_parents(&root);
+
+        let (max_depth, threads) = if ctx.suppress_size && ctx.level() == 1 {
+            (Some(1), 1)
+        } else {
+            (None, ctx.threads.max(1))
+        };
+
+        Some(Self {
+            ctx,
+            root,
+            root_statx,
+            rules,
+            root_dev,
+            max_depth,
+            threads,
+            hidden_mask: mask(ctx, false),
+            displayed_mask,
+        })
+    }
+
+    /// Traverses the root directory, sending each entry that isn't ignored to the receiving end of
+    /// `tx` as a [Node] along with its path. Parent directories are always sent before their
+    /// entries.
+    pub fn scan(self, tx: &Sender<TraversalState>) {
+        let is_dir = self.root_statx.is_dir();
+
+        let root = Entry {
+            path: &self.root,
+            depth: 0,
+            statx: &self.root_statx,
+        };
+
+        let node = Node::from((&root, self.ctx));
+
+        if tx
+            .send(TraversalState::Ongoing(node, self.root.clone()))
+            .is_err()
+            || !is_dir
+        {
+            return;
+        }
+
+        let queue = &Queue::default();
+
+        queue.push(Work {
+            path: self.root.clone(),
+            depth: 0,
+            rules: self.rules.clone(),
+        });
+
+        let scanner = &self;
+
+        thread::scope(|s| {
+            for _ in 0..self.threads {
+                let tx = Sender::clone(tx);
+
+                s.spawn(move || {
+                    while let Some(work) = queue.pop() {
+                        if !scanner.read(&work, queue, &tx) {
+                            queue.quit();
+                        }
+                        queue.done();
+                    }
+                });
+            }
+        });
+    }
+
+    /// Reads the directory of `work`, sending its entries that aren't ignored and queuing its
+    /// subdirectories. Like the portable traversal, the number of entries that weren't ignored is
+    /// sent for every directory that's read and the root directory is read regardless of whether
+    /// traversal was cut short. Returns `false` if traversal was cut short or if the receiving end
+    /// of `tx` hung up.
+    fn read(&self, work: &Work, queue: &Queue, tx: &Sender<TraversalState>) -> bool {
+        if work.depth > 0 && cutoff::is_cut() {
+            return false;
+        }
+
+        let track = cutoff::is_possible(self.ctx);
+
+        let listed = |queued| {
+            !track
+                || tx
+                    .send(TraversalState::Listed(work.path.clone(), queued))
+                    .is_ok()
+        };
+
+        let Ok(dir) = Dir::open(&work.path) else {
+            return listed(0);
+        };
+
+        let Ok(entries) = dir.read() else {
+            return listed(0);
+        };
+
+        let rules = work.rules.add_child(&work.path, |name| {
+            entries
+                .iter()
+                .any(|dirent| dirent.name().to_bytes() == name.as_bytes())
+        });
+
+        let depth = work.depth + 1;
+
+        let mask = if depth <= self.ctx.level() {
+            self.displayed_mask
+        } else {
+            self.hidden_mask
+        };
+
+        let descend = self.max_depth.map_or(true, |max_depth| depth < max_depth);
+
+        let kept = entries
+            .iter()
+            .filter_map(|dirent| {
+                let name = dirent.name();
+                let path = work.path.join(OsStr::from_bytes(name.to_bytes()));
+
+                // The type of the file is only queried ahead of time if the directory entry
+                // omits it.
+                let (is_dir, statx) = if let Some(is_dir) = dirent.is_dir() {
+                    (is_dir, None)
+                } else {
+                    let statx = Statx::at(Some(dir.as_fd()), name, mask).ok()?;
+                    (statx.is_dir(), Some(statx))
+                };
+
+                (!rules.is_ignored(&path, is_dir)).then_some((name, path, statx))
+            })
+            .collect::<Vec<_>>();
+
+        if !listed(kept.len()) {
+            return false;
+        }
+
+        // Entries are visited last to first, the same as the portable traversal which visits them
+        // as it takes them off of its stack, so that entries that are otherwise equal when sorting
+        // keep the same order.
+        for (name, path, statx) in kept.into_iter().rev() {
+            if cutoff::is_cut() {
+                return false;
+            }
+
+            let Ok(statx) = statx.map_or_else(|| Statx::at(Some(dir.as_fd()), name, mask), Ok)
+            else {
+                continue;
+            };
+
+            let is_dir = statx.is_dir();
+
+            let same_fs = self.root_dev.map_or(true, |dev| statx.dev() == dev);
+
+            let entry = Entry {
+                path: &path,
+                depth,
+                statx: &statx,
+            };
+
+            let node = Node::from((&entry, self.ctx));
+
+            if !is_dir {
+                if tx.send(TraversalState::Ongoing(node, path)).is_err() {
+                    return false;
+                }
+                continue;
+            }
+
+            if tx
+                .send(TraversalState::Ongoing(node, path.clone()))
+                .is_err()
+            {
+                return false;
+            }
+
+            if descend && same_fs {
+                queue.push(Work {
+                    path,
+                    depth,
+                    rules: rules.clone(),
+                });
+            } else if track && tx.send(TraversalState::Listed(path, 0)).is_err() {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// What to query of entries depending on whether or not they're `displayed`.
+fn mask(ctx: &Context, displayed: bool) -> u32 {
+    let mut mask = statx::BASIC;
+
+    if !ctx.suppress_size {
+        for disk_usage in ctx
+            .secondary_disk_usages()
+            .iter()
+            .copied()
+            .chain([ctx.disk_usage()])
+        {
+            match disk_usage {
+                DiskUsage::Logical => mask |= statx::SIZE,
+                DiskUsage::Physical | DiskUsage::Block => mask |= statx::BLOCKS,
+                _ => (),
+            }
+        }
+    }
+
+    if ctx.sort.is_by_time() || (ctx.long_view() && displayed) {
+        mask |= statx::TIMES;
+    }
+
+    if ctx.long_view() && displayed {
+        mask |= statx::OWNERSHIP | statx::BLOCKS;
+    }
+
+    mask
+}
+
+impl Entry<'_> {
+    /// The path of the file.
+    pub const fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// The name of the file, which is the whole path for the root directory.
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// The depth of the file relative to the root directory.
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The status of the file.
+    pub const fn statx(&self) -> &Statx {
+        self.statx
+    }
+}
+
+impl ExtendedAttr for Entry<'_> {
+    fn path(&self) -> &Path {
+        self.path
+    }
+}
+
+impl Queue {
+    /// Queues a directory to be read.
+    fn push(&self, work: Work) {
+        if let Ok(mut state) = self.state.lock() {
+            state.work.push(work);
+            state.pending += 1;
+        }
+
+        self.available.notify_one();
+    }
+
+    /// Takes the next directory to be read, waiting for other threads to queue more if there
+    /// aren't any at the moment. Returns `None` once every directory was read or if traversal was
+    /// cut short.
+    fn pop(&self) -> Option<Work> {
+        let mut state = self.state.lock().ok()?;
+
+        loop {
+            if state.quit {
+                return None;
+            }
+
+            if let Some(work) = state.work.pop() {
+                return Some(work);
+            }
+
+            if state.pending == 0 {
+                return None;
+            }
+
+            state = self.available.wait(state).ok()?;
+        }
+    }
+
+    /// Marks a directory that was taken with [`Queue::pop`] as read.
+    fn done(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.pending -= 1;
+
+            if state.pending == 0 {
+                self.available.notify_all();
+            }
+        }
+    }
+
+    /// Cuts traversal short.
+    fn quit(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.quit = true;
+        }
+
+        self.available.notify_all();
+    }
+}
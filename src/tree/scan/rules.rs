@@ -0,0 +1,308 @@
+use crate::{
+    context::Context,
+    tree::{error::Error, ERDTREE_IGNORE},
+};
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    overrides::Override,
+    Match,
+};
+use std::{
+    fs::{self, File, FileType},
+    io::{BufRead, BufReader},
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The ignore rules that apply to the entries of a directory. These mirror the rules that
+/// [`ignore::WalkBuilder`] applies when it's configured by `walk_builder`, including their order of
+/// precedence, so that both traversals find the same entries. The rules of each directory are
+/// linked to those of its parent directory, all the way up to the ancestors of the root directory
+/// whose ignore files apply as well.
+#[derive(Clone)]
+pub struct Rules(Arc<Level>);
+
+/// The rules of a single directory.
+struct Level {
+    shared: Arc<Shared>,
+    parent: Option<Rules>,
+
+    /// Patterns of the custom ignore file, i.e. `.erdignore`.
+    custom: Gitignore,
+
+    /// Patterns of the `.ignore` file.
+    ignore: Gitignore,
+
+    /// Patterns of the `.gitignore` file.
+    git_ignore: Gitignore,
+
+    /// Patterns of `.git/info/exclude`.
+    git_exclude: Gitignore,
+
+    /// Whether the directory is the root of a git repository. Git-specific ignore files only
+    /// apply within git repositories.
+    has_git: bool,
+}
+
+/// The rules that apply regardless of the directory.
+struct Shared {
+    /// Whether hidden files are ignored.
+    hidden: bool,
+
+    /// Whether `.gitignore`, `.git/info/exclude`, and the global gitignore are respected.
+    git_ignore: bool,
+
+    /// Names of the custom ignore files.
+    custom_names: Vec<&'static str>,
+
+    overrides: Override,
+
+    /// Ignore files provided with `--ignore-file`.
+    explicit: Vec<Gitignore>,
+
+    /// The gitignore that's configured globally by `core.excludesFile`.
+    global: Gitignore,
+}
+
+impl Rules {
+    /// The rules that apply before any ignore files are read.
+    pub fn new(ctx: &Context) -> Result<Self, Error> {
+        let git_ignore = !ctx.no_ignore;
+
+        let mut explicit = vec![];
+
+        for ignore_file in &ctx.ignore_file {
+            let path = fs::canonicalize(ignore_file)
+                .map_err(|e| Error::IgnoreFile(format!("{}: {e}", ignore_file.display())))?;
+
+            let mut builder = GitignoreBuilder::new("");
+
+            if let Some(e) = builder.add(&path) {
+                return Err(Error::IgnoreFile(format!("{}: {e}", path.display())));
+            }
+
+            let matcher = builder
+                .build()
+                .map_err(|e| Error::IgnoreFile(format!("{}: {e}", path.display())))?;
+
+            explicit.push(matcher);
+        }
+
+        let shared = Shared {
+            hidden: !ctx.hidden,
+            git_ignore,
+            custom_names: if ctx.no_ignore {
+                vec![]
+            } else {
+                vec![ERDTREE_IGNORE]
+            },
+            overrides: ctx.no_git_override()?,
+            explicit,
+            global: if git_ignore {
+                Gitignore::global().0
+            } else {
+                Gitignore::empty()
+            },
+        };
+
+        Ok(Self(Arc::new(Level {
+            shared: Arc::new(shared),
+            parent: None,
+            custom: Gitignore::empty(),
+            ignore: Gitignore::empty(),
+            git_ignore: Gitignore::empty(),
+            git_exclude: Gitignore::empty(),
+            has_git: false,
+        })))
+    }
+
+    /// Adds the rules of all of the ancestors of `root`, which is expected to be canonical.
+    pub fn add_parents(&self, root: &Path) -> Self {
+        let mut ancestors = root.ancestors().skip(1).collect::<Vec<_>>();
+        ancestors.reverse();
+
+        ancestors.into_iter().fold(self.clone(), |rules, ancestor| {
+            let mut level = rules.level_of(ancestor, |name| ancestor.join(name).exists());
+
+            let git_type = fs::metadata(ancestor.join(".git"))
+                .ok()
+                .map(|md| md.file_type());
+            level.git_exclude = git_exclude(ancestor, git_type);
+            level.has_git = rules.0.shared.git_ignore && git_type.is_some();
+
+            Self(Arc::new(level))
+        })
+    }
+
+    /// Adds the rules of the directory `dir` that apply to its entries. `has_entry` tells
+    /// whether `dir` has an entry of the given name, sparing the need to check for each ignore file.
+    pub fn add_child(&self, dir: &Path, has_entry: impl Fn(&str) -> bool) -> Self {
+        let mut level = self.level_of(dir, &has_entry);
+
+        let git_type = has_entry(".git")
+            .then(|| fs::metadata(dir.join(".git")).ok())
+            .flatten()
+            .map(|md| md.file_type());
+
+        level.git_exclude = git_exclude(dir, git_type);
+        level.has_git = git_type.is_some();
+
+        Self(Arc::new(level))
+    }
+
+    /// Whether the entry at `path` is ignored. Hidden files are only ignored if no other rules
+    /// explicitly include them.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let matched = self.matched(path, is_dir);
+
+        if matched.is_none() && self.0.shared.hidden {
+            return path
+                .file_name()
+                .is_some_and(|name| name.as_bytes().first() == Some(&b'.'));
+        }
+
+        matched.is_ignore()
+    }
+
+    /// Overrides take precedence over everything else, followed by custom ignore files, `.ignore`,
+    /// `.gitignore`, `.git/info/exclude`, the global gitignore, and lastly explicit ignore files.
+    /// The ignore files of nearer directories take precedence over those further up.
+    fn matched(&self, path: &Path, is_dir: bool) -> Match<()> {
+        let shared = &self.0.shared;
+
+        let overridden = shared.overrides.matched(path, is_dir).map(drop);
+
+        if !overridden.is_none() {
+            return overridden;
+        }
+
+        let any_git = self.levels().any(|level| level.has_git);
+
+        let mut custom = Match::None;
+        let mut ignore = Match::None;
+        let mut git_ignore = Match::None;
+        let mut git_exclude = Match::None;
+        let mut saw_git = false;
+
+        for level in self.levels() {
+            if custom.is_none() {
+                custom = level.custom.matched(path, is_dir).map(drop);
+            }
+
+            if ignore.is_none() {
+                ignore = level.ignore.matched(path, is_dir).map(drop);
+            }
+
+            if any_git && !saw_git && git_ignore.is_none() {
+                git_ignore = level.git_ignore.matched(path, is_dir).map(drop);
+            }
+
+            if any_git && !saw_git && git_exclude.is_none() {
+                git_exclude = level.git_exclude.matched(path, is_dir).map(drop);
+            }
+
+            saw_git = saw_git || level.has_git;
+        }
+
+        let global = if any_git {
+            shared.global.matched(path, is_dir).map(drop)
+        } else {
+            Match::None
+        };
+
+        let explicit = shared
+            .explicit
+            .iter()
+            .rev()
+            .map(|gitignore| gitignore.matched(path, is_dir).map(drop))
+            .find(|matched| !matched.is_none())
+            .unwrap_or(Match::None);
+
+        custom
+            .or(ignore)
+            .or(git_ignore)
+            .or(git_exclude)
+            .or(global)
+            .or(explicit)
+    }
+
+    /// The rules of the directory `dir` whose parent's rules are `self`, without regard for
+    /// whether `dir` is in a git repository.
+    fn level_of(&self, dir: &Path, has_entry: impl Fn(&str) -> bool) -> Level {
+        let shared = Arc::clone(&self.0.shared);
+
+        let git_ignore = if shared.git_ignore {
+            gitignore(dir, dir, &[".gitignore"], &has_entry)
+        } else {
+            Gitignore::empty()
+        };
+
+        Level {
+            custom: gitignore(dir, dir, &shared.custom_names, &has_entry),
+            ignore: gitignore(dir, dir, &[".ignore"], &has_entry),
+            git_ignore,
+            git_exclude: Gitignore::empty(),
+            has_git: false,
+            parent: Some(self.clone()),
+            shared,
+        }
+    }
+
+    /// The rules of this directory followed by those of its ancestors.
+    fn levels(&self) -> impl Iterator<Item = &Level> {
+        std::iter::successors(Some(self), |rules| rules.0.parent.as_ref()).map(|rules| &*rules.0)
+    }
+}
+
+/// Builds a [`Gitignore`] rooted at `dir` from the files of the given `names` in `file_dir` that
+/// exist according to `exists`. Files that can't be read or parsed are skipped.
+fn gitignore(
+    dir: &Path,
+    file_dir: &Path,
+    names: &[&str],
+    exists: impl Fn(&str) -> bool,
+) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(dir);
+
+    for name in names.iter().filter(|name| exists(name)) {
+        builder.add(file_dir.join(name));
+    }
+
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Builds a [`Gitignore`] from the `info/exclude` of the git repository at `dir`, if any. `git_type`
+/// is the file type of its `.git`.
+fn git_exclude(dir: &Path, git_type: Option<FileType>) -> Gitignore {
+    let Some(git_dir) = git_type.and_then(|ft| git_common_dir(dir, ft)) else {
+        return Gitignore::empty();
+    };
+
+    gitignore(dir, &git_dir, &["info/exclude"], |name| {
+        git_dir.join(name).exists()
+    })
+}
+
+/// The git directory whose `info/exclude` applies to the repository at `dir`. In worktrees `.git`
+/// is a file that points to the actual git directory, which may in turn point to the common git
+/// directory that's shared by all worktrees.
+fn git_common_dir(dir: &Path, git_type: FileType) -> Option<PathBuf> {
+    let git_dir = dir.join(".git");
+
+    if !git_type.is_file() {
+        return Some(git_dir);
+    }
+
+    let first_line = |path: &Path| BufReader::new(File::open(path).ok()?).lines().next()?.ok();
+
+    let real_git_dir = PathBuf::from(first_line(&git_dir)?.strip_prefix("gitdir: ")?);
+
+    let common_dir = first_line(&real_git_dir.join("commondir"))?;
+
+    if common_dir.starts_with('.') {
+        Some(real_git_dir.join(common_dir))
+    } else {
+        Some(PathBuf::from(common_dir))
+    }
+}
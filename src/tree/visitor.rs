@@ -1,62 +1,283 @@
 use std::sync::mpsc::Sender;
 
-use crate::{tree::node::Node, Context};
-use ignore::{DirEntry, Error as IgnoreError, ParallelVisitor, ParallelVisitorBuilder, WalkState};
+use super::{cutoff, Predicate};
+use crate::{
+    context::MetadataMatcher,
+    fs::link::{self, Chain, End},
+    tree::node::Node,
+    Context,
+};
+use ignore::{
+    DirEntry, Error as IgnoreError, ParallelVisitor, ParallelVisitorBuilder, WalkBuilder, WalkState,
+};
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
+// Boxing the node of every entry to shrink the rarer variants isn't worth the allocation.
+#[allow(clippy::large_enum_variant)]
 pub enum TraversalState {
-    Ongoing(Node),
+    /// A [Node] along with its path, which the [Node] itself doesn't retain.
+    Ongoing(Node, PathBuf),
+
+    /// The directory at the path was read and the given number of its entries were queued for
+    /// traversal. Every directory that's sent is eventually followed by this, unless traversal is
+    /// cut short, so that it can be told whether everything beneath it was received.
+    Listed(PathBuf, usize),
+
     Done,
 }
 
 pub struct Branch<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+
+    /// The predicate of the traversal, which symlinks that can't be followed are recovered with.
+    predicate: Arc<Option<Predicate>>,
+
+    /// Tests directories against the metadata filters, which the predicate lets through
+    /// regardless.
+    matcher: Arc<Option<MetadataMatcher>>,
+
+    /// The unfollowed symlinks of the directory that a symlink was last recovered from.
+    links: Option<(PathBuf, HashMap<PathBuf, DirEntry>)>,
+
+    /// The directory that was last visited on this thread, which the walker goes on to read.
+    listing: Option<PathBuf>,
+}
+
+thread_local! {
+    /// The number of entries that the walker queued while reading the directory that was last
+    /// visited on this thread. The walker reads a directory on the same thread that visited it
+    /// and before visiting anything else.
+    static QUEUED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts an entry that the walker is about to queue, which is expected to be called from the
+/// predicate of the walker once the entry passes every filter.
+pub fn count_queued() {
+    QUEUED.with(|queued| queued.set(queued.get() + 1));
 }
 
 pub struct BranchVisitorBuilder<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+    predicate: Arc<Option<Predicate>>,
+    matcher: Arc<Option<MetadataMatcher>>,
 }
 
 impl<'a> BranchVisitorBuilder<'a> {
     pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+        // The walker was already built with the same predicate so it can't fail here.
+        let predicate = if ctx.follow {
+            super::walk_predicate(ctx).ok().flatten()
+        } else {
+            None
+        };
+
+        // Directories aren't tested against `--pattern` so they can't satisfy it on their own.
+        #[cfg(unix)]
+        let matcher = if ctx.filters_metadata() && ctx.pattern.is_none() {
+            ctx.metadata_matcher().ok()
+        } else {
+            None
+        };
+
+        #[cfg(not(unix))]
+        let matcher = None;
+
+        Self {
+            ctx,
+            tx,
+            predicate: Arc::new(predicate),
+            matcher: Arc::new(matcher),
+        }
     }
 }
 
 impl<'a> Branch<'a> {
-    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+    pub fn new(
+        ctx: &'a Context,
+        tx: Sender<TraversalState>,
+        predicate: Arc<Option<Predicate>>,
+        matcher: Arc<Option<MetadataMatcher>>,
+    ) -> Self {
+        Self {
+            ctx,
+            tx,
+            predicate,
+            matcher,
+            links: None,
+            listing: None,
+        }
     }
 }
 
-impl From<Node> for TraversalState {
-    fn from(node: Node) -> Self {
-        Self::Ongoing(node)
+impl Branch<'_> {
+    /// Reports how many entries were queued beneath the directory that was last visited, which
+    /// the walker is done reading once anything else is visited or once this thread is done.
+    fn flush_listing(&mut self) {
+        if let Some(dir) = self.listing.take() {
+            let queued = QUEUED.with(Cell::take);
+            let _ = self.tx.send(TraversalState::Listed(dir, queued));
+        }
+    }
+
+    /// When following symlinks, the traversal reports symlinks that loop back onto an ancestor or
+    /// that can't be resolved as errors. This recovers such a symlink as an unfollowed entry.
+    fn recover(&mut self, err: &IgnoreError) -> Option<(DirEntry, usize)> {
+        self.unfollowed(link::error_path(err)?)
+    }
+
+    /// The symlink at `path` as an unfollowed entry along with its depth, subject to the predicate
+    /// of the traversal and to whether hidden files are shown. The directory that contains the
+    /// symlink is read once for all of its symlinks, as the walker reports every entry of a
+    /// directory on the thread that reads it.
+    fn unfollowed(&mut self, path: &Path) -> Option<(DirEntry, usize)> {
+        let parent = path.parent()?;
+
+        let depth = path
+            .strip_prefix(self.ctx.dir_canonical())
+            .ok()?
+            .components()
+            .count();
+
+        let is_hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+
+        if is_hidden && !self.ctx.hidden {
+            return None;
+        }
+
+        if self.links.as_ref().map_or(true, |(dir, _)| dir != parent) {
+            let links = WalkBuilder::new(parent)
+                .standard_filters(false)
+                .max_depth(Some(1))
+                .build()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.depth() == 1 && entry.path_is_symlink())
+                .map(|entry| (entry.path().to_path_buf(), entry))
+                .collect();
+
+            self.links = Some((parent.to_path_buf(), links));
+        }
+
+        let (_, links) = self.links.as_mut()?;
+        let dir_entry = links.remove(path)?;
+
+        let keep = self
+            .predicate
+            .as_ref()
+            .as_ref()
+            .map_or(true, |predicate| predicate(&dir_entry));
+
+        keep.then_some((dir_entry, depth))
     }
 }
 
 impl ParallelVisitor for Branch<'_> {
     fn visit(&mut self, entry: Result<DirEntry, IgnoreError>) -> WalkState {
-        let Ok(dir_entry) = entry else {
-            return WalkState::Skip;
+        // Errors may be visited while the walker is still reading the last visited directory.
+        if entry.is_ok() {
+            self.flush_listing();
+        }
+
+        // The root directory is always visited so that there's something to show.
+        if cutoff::is_cut() && entry.as_ref().map_or(true, |e| e.depth() > 0) {
+            return WalkState::Quit;
+        }
+
+        // Symlinks that can't be followed are kept and flagged rather than dropped.
+        let (dir_entry, depth, recovered) = match entry {
+            // The walker only catches symlinks that loop back onto an ancestor within the root so
+            // those that loop back onto an ancestor of the root are kept from being descended into
+            // here.
+            Ok(dir_entry) if self.ctx.follow && loops_to_ancestor(&dir_entry, self.ctx) => {
+                match self.unfollowed(dir_entry.path()) {
+                    Some((dir_entry, depth)) => (dir_entry, depth, true),
+                    None => return WalkState::Skip,
+                }
+            },
+            Ok(dir_entry) => {
+                let depth = dir_entry.depth();
+                (dir_entry, depth, false)
+            },
+            // The walker reports errors while reading the directory that the entry belongs to, so
+            // a recovered entry is counted as queued beneath it like any other.
+            Err(err) if self.ctx.follow => match self.recover(&err) {
+                Some((dir_entry, depth)) => {
+                    count_queued();
+                    (dir_entry, depth, true)
+                },
+                None => return WalkState::Skip,
+            },
+            Err(_) => return WalkState::Skip,
         };
 
-        match Node::try_from((dir_entry, self.ctx)) {
-            Ok(node) => {
-                if self.tx.send(TraversalState::from(node)).is_err() {
+        match Node::try_from((&dir_entry, depth, self.ctx)) {
+            Ok(mut node) => {
+                if recovered {
+                    node.resolve_link_chain(dir_entry.path(), self.ctx.dir_canonical());
+                }
+
+                let is_dir = !recovered && dir_entry.file_type().is_some_and(|ft| ft.is_dir());
+
+                if let Some(matches) = self.matcher.as_ref() {
+                    if is_dir && dir_entry.metadata().is_ok_and(|md| matches(&md)) {
+                        node.mark_matched();
+                    }
+                }
+
+                let path = dir_entry.into_path();
+
+                if is_dir && cutoff::is_possible(self.ctx) {
+                    QUEUED.with(|queued| queued.set(0));
+                    self.listing = Some(path.clone());
+                }
+
+                let state = TraversalState::Ongoing(node, path);
+
+                if self.tx.send(state).is_err() {
                     return WalkState::Quit;
                 }
-                WalkState::Continue
+
+                if recovered {
+                    WalkState::Skip
+                } else {
+                    WalkState::Continue
+                }
             },
             _ => WalkState::Skip,
         }
     }
 }
 
+/// Returns `true` if `dir_entry` is a followed symlink whose target is a directory that contains
+/// it, which would have its contents traversed again beneath it.
+fn loops_to_ancestor(dir_entry: &DirEntry, ctx: &Context) -> bool {
+    dir_entry.path_is_symlink()
+        && dir_entry.file_type().is_some_and(|ft| ft.is_dir())
+        && Chain::resolve(dir_entry.path(), ctx.dir_canonical()).end() == End::Ancestor
+}
+
+impl Drop for Branch<'_> {
+    fn drop(&mut self) {
+        self.flush_listing();
+    }
+}
+
 impl<'s> ParallelVisitorBuilder<'s> for BranchVisitorBuilder<'s> {
     fn build(&mut self) -> Box<dyn ParallelVisitor + 's> {
-        let visitor = Branch::new(self.ctx, self.tx.clone());
+        let visitor = Branch::new(
+            self.ctx,
+            self.tx.clone(),
+            Arc::clone(&self.predicate),
+            Arc::clone(&self.matcher),
+        );
         Box::new(visitor)
     }
 }
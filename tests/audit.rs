@@ -0,0 +1,52 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use std::{
+        fs::{self, File, Permissions},
+        os::unix::fs::PermissionsExt,
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn audit() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let bin = tmp.path().join("bin");
+        let docs = tmp.path().join("docs");
+        fs::create_dir(&bin)?;
+        fs::create_dir(&docs)?;
+
+        for (path, mode) in [
+            (bin.join("ls"), 0o755),
+            (bin.join("su"), 0o4755),
+            (docs.join("notes"), 0o666),
+            (docs.join("readme"), 0o644),
+        ] {
+            File::create(&path)?;
+            fs::set_permissions(&path, Permissions::from_mode(mode))?;
+        }
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--audit", "--audit-only", &dir]);
+
+        let name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ notes [world-writable]
+ ┌─ docs
+ │  ┌─ su [setuid]
+ ├─ bin
+ {name}
+
+2 directories, 1 file, 1 executable
+
+Audit: 1 setuid, 1 world-writable"
+            )
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn branches_ascii() {
+    assert_eq!(
+        utils::run_cmd(&["--branches", "ascii", "--level", "2", "tests/data"]),
+        indoc!(
+            "143 B    ,- cassildas_song.md
+             143 B ,- the_yellow_king
+             100 B |- nylarlathotep.txt
+             161 B |- nemesis.txt
+              83 B |- necronomicon.txt
+             446 B |  ,- lipsum.txt
+             446 B |- lipsum
+             308 B |  ,- polaris.txt
+             308 B |- dream_cycle
+            1241 B data
+
+            3 directories, 6 files"
+        ),
+        "Failed to draw branches using ASCII characters"
+    )
+}
+
+#[test]
+fn branches_compact() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--branches",
+            "compact",
+            "--layout",
+            "inverted",
+            "--level",
+            "2",
+            "tests/data"
+        ]),
+        indoc!(
+            "1241 B data
+             308 B ├ dream_cycle
+             308 B │ └ polaris.txt
+             446 B ├ lipsum
+             446 B │ └ lipsum.txt
+              83 B ├ necronomicon.txt
+             161 B ├ nemesis.txt
+             100 B ├ nylarlathotep.txt
+             143 B └ the_yellow_king
+             143 B   └ cassildas_song.md
+
+            3 directories, 6 files"
+        ),
+        "Failed to draw branches with a two column indent"
+    )
+}
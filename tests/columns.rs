@@ -0,0 +1,65 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn columns() {
+    let out = utils::run_cmd(&[
+        "--columns",
+        "nlink,size",
+        "--glob",
+        "--pattern",
+        "*.txt",
+        "--layout",
+        "flat",
+        "tests/data",
+    ]);
+
+    // The hardlink count of directories varies between filesystems so only files are compared.
+    let files = out
+        .lines()
+        .filter(|line| line.ends_with(".txt"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_eq!(
+        files,
+        indoc!(
+            "1  100 B   nylarlathotep.txt
+            1  161 B   nemesis.txt
+            1   83 B   necronomicon.txt
+            1  446 B   lipsum/lipsum.txt
+            1  308 B   dream_cycle/polaris.txt"
+        )
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn columns_octal() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{
+        fs::{self, File, Permissions},
+        os::unix::fs::PermissionsExt,
+    };
+    use tempfile::TempDir;
+
+    let tmp = TempDir::new()?;
+    let file = tmp.path().join("file");
+
+    File::create(&file)?;
+    fs::set_permissions(&file, Permissions::from_mode(0o640))?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+
+    let out = utils::run_cmd(&["--columns", "perms,size", "--octal", &dir]);
+
+    assert_eq!(out.lines().next(), Some("0640 0 B ┌─ file"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn columns_octal_without_long_view() {
+    utils::run_cmd(&["--columns", "size", "--octal", "tests/data"]);
+}
@@ -0,0 +1,87 @@
+use indoc::indoc;
+use std::{error::Error, fs::File};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn multiple_metrics() {
+    assert_eq!(
+        utils::run_cmd(&["--disk-usage", "logical,line", "tests/data"]),
+        indoc!(
+            "143 B  6    ┌─ cassildas_song.md
+             143 B  6 ┌─ the_yellow_king
+             100 B  1 ├─ nylarlathotep.txt
+             161 B  4 ├─ nemesis.txt
+              83 B  2 ├─ necronomicon.txt
+             446 B  1 │  ┌─ lipsum.txt
+             446 B  1 ├─ lipsum
+             308 B 10 │  ┌─ polaris.txt
+             308 B 10 ├─ dream_cycle
+            1241 B 24 data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn sort_metric() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--disk-usage",
+            "line,logical",
+            "--sort",
+            "size",
+            "--sort-metric",
+            "logical",
+            "tests/data"
+        ]),
+        indoc!(
+            "2   83 B ┌─ necronomicon.txt
+             1  100 B ├─ nylarlathotep.txt
+             6  143 B │  ┌─ cassildas_song.md
+             6  143 B ├─ the_yellow_king
+             4  161 B ├─ nemesis.txt
+            10  308 B │  ┌─ polaris.txt
+            10  308 B ├─ dream_cycle
+             1  446 B │  ┌─ lipsum.txt
+             1  446 B ├─ lipsum
+            24 1241 B data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+#[should_panic]
+fn sort_metric_not_displayed() {
+    utils::run_cmd(&[
+        "--disk-usage",
+        "line,word",
+        "--sort-metric",
+        "logical",
+        "tests/data",
+    ]);
+}
+
+#[test]
+fn ratio() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    // A file that's all holes takes up no space on disk.
+    File::create(tmp.path().join("sparse"))?.set_len(1024 * 1024)?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+
+    // The first line is the root directory whose name is random.
+    let out = utils::run_cmd(&["--ratio", "--layout", "iflat", &dir]);
+
+    assert_eq!(
+        out.lines().skip(1).map(str::trim).collect::<Vec<_>>(),
+        ["1048576 B 0%   sparse", "", "1 file"]
+    );
+
+    Ok(())
+}
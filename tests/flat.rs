@@ -61,3 +61,20 @@ fn flat_with_level() {
         )
     )
 }
+
+#[cfg(unix)]
+#[test]
+fn flat_long() {
+    let out = utils::run_cmd(&["--layout", "flat", "--long", "tests/data"]);
+
+    assert!(
+        out.lines()
+            .any(|line| line.ends_with("    100 B nylarlathotep.txt")),
+        "Failed to set disk usage apart from the long view"
+    );
+
+    assert!(
+        out.lines().any(|line| line.ends_with("   1241 B data")),
+        "Failed to set disk usage apart from the long view"
+    );
+}
@@ -0,0 +1,78 @@
+use indoc::indoc;
+use std::{error::Error, fs, io::Write};
+use tempfile::{Builder, TempDir};
+
+mod utils;
+
+#[test]
+fn ignore_file() -> Result<(), Box<dyn Error>> {
+    let mut ignore_file = Builder::new().suffix(".ignore").tempfile()?;
+    writeln!(ignore_file, "*.txt")?;
+
+    let path = ignore_file.path().to_string_lossy().into_owned();
+
+    assert_eq!(
+        utils::run_cmd(&["--ignore-file", &path, "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+            143 B ┌─ the_yellow_king
+                - ├─ lipsum
+                - ├─ dream_cycle
+            143 B data
+
+            3 directories, 1 file"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn erdignore() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::create_dir_all(root.join("logs"))?;
+    fs::create_dir_all(root.join("src"))?;
+
+    let files = [
+        (".erdignore", "logs\n*.tmp\n"),
+        ("logs/out.log", "out\n"),
+        ("notes.txt", "notes\n"),
+        ("scratch.tmp", "scratch\n"),
+        ("src/.erdignore", "gen.rs\n"),
+        ("src/gen.rs", "gen\n"),
+        ("src/lib.rs", "lib\n"),
+    ];
+
+    for (path, contents) in files {
+        fs::write(root.join(path), contents)?;
+    }
+
+    let dir = root.to_string_lossy().into_owned();
+
+    // The first line is the root directory whose name is random.
+    let run = |args: &[&str]| {
+        utils::run_cmd(&[args, &["--layout", "iflat", "--suppress-size", &dir]].concat())
+            .lines()
+            .skip(1)
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    assert_eq!(
+        run(&[]),
+        indoc!(
+            "notes.txt
+            src
+            src/lib.rs
+
+            1 directory, 2 files"
+        )
+    );
+
+    assert!(run(&["--no-ignore"]).ends_with("2 directories, 5 files"));
+
+    Ok(())
+}
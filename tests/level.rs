@@ -1,4 +1,5 @@
 use indoc::indoc;
+use std::fs;
 
 mod utils;
 
@@ -20,3 +21,39 @@ fn level() {
         "Failed to print at max level of 1."
     )
 }
+
+#[test]
+fn level_zero() {
+    assert_eq!(
+        utils::run_cmd(&["--level", "0", "tests/data"]),
+        indoc!(
+            "1241 B data
+
+            3 directories, 6 files"
+        ),
+        "Failed to account for everything beneath the root at max level of 0."
+    )
+}
+
+#[test]
+fn level_hardlinks() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    let nested = tmp.path().join("kadath").join("ulthar");
+    fs::create_dir_all(&nested)?;
+
+    let src = tmp.path().join("kadath").join("celephais.txt");
+    fs::write(&src, "0123456789")?;
+    fs::hard_link(&src, nested.join("curwin.hpl"))?;
+    fs::write(nested.join("sarkomand.txt"), "01234")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let name = tmp.path().file_name().unwrap().to_string_lossy();
+
+    assert_eq!(
+        utils::run_cmd(&["--level", "1", &dir]),
+        format!("15 B ┌─ kadath\n15 B {name}\n\n2 directories, 3 files"),
+        "Failed to count hard-links beyond the max level once."
+    );
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use std::{
+        fs::{self, File},
+        os::unix::fs::symlink,
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn link_chain() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+
+        fs::create_dir(tmp.path().join("dir"))?;
+        File::create(tmp.path().join("file"))?;
+        symlink("b", tmp.path().join("a"))?;
+        symlink("file", tmp.path().join("b"))?;
+        symlink("/dev/null", tmp.path().join("abs"))?;
+        symlink("y", tmp.path().join("x"))?;
+        symlink("x", tmp.path().join("y"))?;
+        symlink("..", tmp.path().join("dir").join("up"))?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--link-chain", &dir]);
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ y \u{2192} x \u{2192} y [cycle]
+ ├─ x \u{2192} y \u{2192} x [cycle]
+ ├─ file
+ │  ┌─ up \u{2192} .. [loops to ancestor]
+ ├─ dir
+ ├─ b \u{2192} file
+ ├─ abs \u{21D2} /dev/null [outside root]
+ ├─ a \u{2192} b \u{2192} file
+ {name}
+
+1 directory, 1 file, 4 links, 2 broken links"
+            ),
+            "Failed to print symlink resolution chains"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn link_loops_when_following() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+
+        fs::create_dir(tmp.path().join("dir"))?;
+        symlink("..", tmp.path().join("dir").join("up"))?;
+        symlink("missing", tmp.path().join("orphan"))?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--follow", &dir]);
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ orphan \u{2192} missing [broken]
+ │  ┌─ up \u{2192} .. [loops to ancestor]
+ ├─ dir
+ {name}
+
+1 directory, 1 link, 1 broken link"
+            ),
+            "Failed to flag symlinks that can't be followed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn link_loops_above_root_when_following() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().join("root");
+
+        fs::create_dir_all(root.join("sub"))?;
+        fs::write(root.join("a"), "abc")?;
+        symlink("../..", root.join("sub").join("up"))?;
+
+        let dir = root.to_string_lossy().into_owned();
+
+        let out = super::utils::run_cmd(&["--follow", &dir]);
+
+        assert_eq!(
+            out,
+            "-    ┌─ up \u{2192} ../.. [loops to ancestor, outside root]
+  - ┌─ sub
+3 B ├─ a
+3 B root
+
+1 directory, 1 file, 1 link",
+            "Failed to keep from descending into a symlink to an ancestor of the root"
+        );
+
+        Ok(())
+    }
+}
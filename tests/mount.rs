@@ -0,0 +1,40 @@
+mod utils;
+
+#[cfg(target_os = "linux")]
+mod test {
+    use std::{error::Error, fs};
+
+    #[test]
+    fn root_mount_point() -> Result<(), Box<dyn Error>> {
+        let mountinfo = fs::read_to_string("/proc/self/mountinfo")?;
+
+        // Mounts stacked on the same mount point take the place of earlier ones.
+        let Some((fs_type, source)) = mountinfo.lines().rev().find_map(|line| {
+            let (fields, rest) = line.split_once(" - ")?;
+            let mut rest = rest.split(' ');
+            let (fs_type, source) = (rest.next()?, rest.next()?);
+
+            (fields.split(' ').nth(4)? == "/dev").then_some((fs_type, source))
+        }) else {
+            return Ok(());
+        };
+
+        let out = super::utils::run_cmd(&[
+            "--suppress-size",
+            "--one-file-system",
+            "--dirs-only",
+            "--level",
+            "1",
+            "/dev",
+        ]);
+
+        let root = format!("dev [{fs_type} {source}]");
+
+        assert!(
+            out.lines().any(|line| line.trim() == root),
+            "Failed to annotate the root with its filesystem"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use std::{
+        ffi::CString,
+        fs::{self, File},
+        os::unix::ffi::OsStrExt,
+        path::Path,
+    };
+    use tempfile::TempDir;
+
+    /// An id that is unlikely to belong to any user or group.
+    const ORPHANED_ID: u32 = 4_242_424;
+
+    /// Changes the owner and group of `path`, which is only permitted for privileged users.
+    fn chown(path: &Path, id: u32) -> Result<bool, Box<dyn std::error::Error>> {
+        let path = CString::new(path.as_os_str().as_bytes())?;
+        Ok(unsafe { libc::chown(path.as_ptr(), id, id) } == 0)
+    }
+
+    #[test]
+    fn numeric_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        File::create(tmp.path().join("file"))?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--long", "--numeric-ids", &dir]);
+
+        let uid = unsafe { libc::geteuid() };
+
+        assert!(
+            out.lines()
+                .take_while(|line| !line.trim().is_empty())
+                .all(|line| line.split_whitespace().nth(1) == Some(&uid.to_string())),
+            "Failed to show numeric ids: {out}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn orphaned_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let kept = tmp.path().join("kept");
+        let removed = tmp.path().join("removed");
+        fs::create_dir(&kept)?;
+        fs::create_dir(&removed)?;
+        File::create(kept.join("mine"))?;
+
+        let orphan = removed.join("orphan");
+        File::create(&orphan)?;
+
+        if !chown(&orphan, ORPHANED_ID)? {
+            return Ok(());
+        }
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--no-user", &dir]);
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ orphan
+ ┌─ removed
+ {name}
+
+1 directory, 1 file"
+            )
+        );
+
+        let out = super::utils::run_cmd(&[
+            "--suppress-size",
+            "--columns",
+            "owner,group",
+            "--no-user",
+            &dir,
+        ]);
+
+        assert!(
+            out.starts_with(&format!("{ORPHANED_ID} {ORPHANED_ID}")),
+            "Failed to fall back to numeric ids: {out}"
+        );
+
+        Ok(())
+    }
+}
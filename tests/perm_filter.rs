@@ -0,0 +1,132 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use std::{
+        fs::{self, File, Permissions},
+        os::unix::fs::PermissionsExt,
+    };
+    use tempfile::TempDir;
+
+    fn setup() -> Result<TempDir, Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let bin = tmp.path().join("bin");
+        let docs = tmp.path().join("docs");
+        fs::create_dir(&bin)?;
+        fs::create_dir(&docs)?;
+
+        for (path, mode) in [
+            (bin.join("lib"), 0o644),
+            (bin.join("run"), 0o755),
+            (docs.join("notes"), 0o666),
+            (docs.join("readme"), 0o644),
+        ] {
+            File::create(&path)?;
+            fs::set_permissions(&path, Permissions::from_mode(mode))?;
+        }
+
+        Ok(tmp)
+    }
+
+    #[test]
+    fn perm() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = setup()?;
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        // Directories are matched on their own permissions too.
+        let out = super::utils::run_cmd(&["--suppress-size", "--perm", "755", &dir]);
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ docs
+ │  ┌─ run
+ ├─ bin
+ {name}
+
+2 directories, 1 executable"
+            )
+        );
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--perm", "-o+w", &dir]);
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ notes
+ ┌─ docs
+ {name}
+
+1 directory, 1 file"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn executable() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = setup()?;
+        let dir = tmp.path().to_string_lossy().into_owned();
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--executable", &dir]);
+
+        assert!(
+            out.starts_with("┌─ run\n ┌─ bin\n"),
+            "Failed to filter for executables: {out}"
+        );
+        assert!(!out.contains("docs"), "Failed to prune directories: {out}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn writable_by() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = setup()?;
+        let dir = tmp.path().to_string_lossy().into_owned();
+
+        // An id that's unlikely to own the files, leaving only what others may write to.
+        let out = super::utils::run_cmd(&["--suppress-size", "--writable-by", "65534", &dir]);
+
+        assert!(
+            out.starts_with("┌─ notes\n ┌─ docs\n"),
+            "Failed to filter for writable files: {out}"
+        );
+        assert!(!out.contains("bin"), "Failed to prune directories: {out}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn perm_directory() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = setup()?;
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let shared = tmp.path().join("shared");
+        fs::create_dir(&shared)?;
+        File::create(shared.join("draft"))?;
+        fs::set_permissions(shared.join("draft"), Permissions::from_mode(0o644))?;
+        fs::set_permissions(&shared, Permissions::from_mode(0o777))?;
+        fs::set_permissions(
+            tmp.path().join("docs").join("notes"),
+            Permissions::from_mode(0o644),
+        )?;
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--perm", "-o+w", &dir]);
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ shared
+ {name}
+
+1 directory"
+            ),
+            "Failed to match a directory on its own permissions"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+mod utils;
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+mod test {
+    use super::utils;
+    use indoc::indoc;
+    use std::{error::Error, fs, path::Path};
+    use tempfile::TempDir;
+
+    /// Runs erd with both scanners, asserting that they agree, and returns the output.
+    fn run_both(args: &[&str]) -> String {
+        let walk = utils::run_cmd(&[&["--scanner", "walk"], args].concat());
+        let getdents = utils::run_cmd(&[&["--scanner", "getdents"], args].concat());
+
+        assert_eq!(walk, getdents, "scanners disagree for {args:?}");
+
+        getdents
+    }
+
+    /// A git repository with ignore files at various levels, negated patterns, and hidden files.
+    fn repository(root: &Path) -> Result<(), Box<dyn Error>> {
+        for dir in [
+            ".git/info",
+            "build/out",
+            "docs/drafts",
+            "docs/.notes",
+            "src/gen",
+        ] {
+            fs::create_dir_all(root.join(dir))?;
+        }
+
+        let files = [
+            (".git/info/exclude", "secret*\n"),
+            (".gitignore", "*.log\n!keep.log\nbuild/\n"),
+            (".ignore", "docs/drafts/\n"),
+            (".env", "KEY=value\n"),
+            ("a.log", "a\n"),
+            ("keep.log", "keep\n"),
+            ("secret.txt", "hush\n"),
+            ("build/out/bin", "bin\n"),
+            ("docs/.notes/todo", "todo\n"),
+            ("docs/drafts/wip.md", "wip\n"),
+            ("docs/readme.md", "readme\n"),
+            ("src/.erdignore", "gen\n"),
+            ("src/.gitignore", "*.tmp\n!keep.tmp\n"),
+            ("src/gen/code.rs", "code\n"),
+            ("src/lib.rs", "lib\n"),
+            ("src/scratch.tmp", "scratch\n"),
+            ("src/keep.tmp", "keep\n"),
+        ];
+
+        for (path, contents) in files {
+            fs::write(root.join(path), contents)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn scanner() {
+        assert_eq!(
+            run_both(&["tests/data"]),
+            indoc!(
+                "143 B    ┌─ cassildas_song.md
+                143 B ┌─ the_yellow_king
+                100 B ├─ nylarlathotep.txt
+                161 B ├─ nemesis.txt
+                 83 B ├─ necronomicon.txt
+                446 B │  ┌─ lipsum.txt
+                446 B ├─ lipsum
+                308 B │  ┌─ polaris.txt
+                308 B ├─ dream_cycle
+               1241 B data
+
+               3 directories, 6 files"
+            )
+        );
+    }
+
+    #[test]
+    fn scanner_ignore_rules() -> Result<(), Box<dyn Error>> {
+        let tmp = TempDir::new()?;
+        repository(tmp.path())?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+
+        // The first line is the root directory whose name is random.
+        let out = run_both(&["--layout", "iflat", "--suppress-size", &dir]);
+
+        assert_eq!(
+            out.lines()
+                .skip(1)
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            indoc!(
+                "docs
+                docs/readme.md
+                keep.log
+                src
+                src/keep.tmp
+                src/lib.rs
+
+                2 directories, 4 files"
+            )
+        );
+
+        for args in [
+            &["--hidden"][..],
+            &["--hidden", "--no-git"],
+            &["--no-ignore"],
+            &["--no-ignore", "--hidden"],
+            &["--level", "1"],
+            &["--dirs-only"],
+            &["--prune", "--hidden"],
+        ] {
+            run_both(&[args, &["--layout", "iflat", "--suppress-size", &dir]].concat());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn scanner_fallback() {
+        for args in [
+            &["--follow"][..],
+            &["--pattern", "*.txt", "--glob"],
+            &["--perm", "-u+r"],
+        ] {
+            run_both(&[args, &["tests/data"]].concat());
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn sloc_summary() {
+    assert_eq!(
+        utils::run_cmd(&["--disk-usage", "sloc", "--sloc-summary", "tests/data"]),
+        indoc!(
+            "5    ┌─ cassildas_song.md
+            5 ┌─ the_yellow_king
+            - ├─ nylarlathotep.txt
+            - ├─ nemesis.txt
+            - ├─ necronomicon.txt
+            - │  ┌─ lipsum.txt
+            - ├─ lipsum
+            - │  ┌─ polaris.txt
+            - ├─ dream_cycle
+            5 data
+
+            3 directories, 6 files
+
+            Language  Files  Code  Comments  Blanks
+            Markdown      1     5         0       1
+            Total         1     5         0       1"
+        )
+    )
+}
@@ -0,0 +1,52 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use std::{
+        fs::{self, File, Permissions},
+        os::unix::{
+            fs::{symlink, PermissionsExt},
+            net::UnixListener,
+        },
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn special_files() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+
+        File::create(tmp.path().join("file"))?;
+        File::create(tmp.path().join("script"))?;
+        fs::set_permissions(tmp.path().join("script"), Permissions::from_mode(0o755))?;
+        symlink("file", tmp.path().join("link"))?;
+        symlink("missing", tmp.path().join("orphan"))?;
+        let _listener = UnixListener::bind(tmp.path().join("socket"))?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let out = super::utils::run_cmd(&["--suppress-size", &dir]);
+
+        assert!(
+            out.ends_with("1 file, 1 executable, 1 link, 1 broken link, 1 socket"),
+            "Failed to count special files separately: {out}"
+        );
+
+        let out = super::utils::run_cmd(&[
+            "--suppress-size",
+            "--file-type",
+            "broken-link",
+            "--pattern",
+            ".",
+            &dir,
+        ]);
+
+        assert_eq!(
+            out,
+            format!("┌─ orphan \u{2192} missing\n {name}\n\n1 broken link"),
+            "Failed to filter on broken links"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use std::{
+        error::Error,
+        ffi::CString,
+        fs::{self, File},
+        os::unix::{ffi::OsStrExt, fs::MetadataExt},
+        path::Path,
+        ptr,
+    };
+    use tempfile::TempDir;
+
+    /// Seconds since the epoch that the file is set to have last been modified.
+    const MODIFIED: i64 = 1_000_000_000;
+
+    /// Sets the access and modification times of `path` to `secs` since the epoch which in turn
+    /// sets its status change time to the current time.
+    fn backdate(path: &Path, secs: i64) -> Result<(), Box<dyn Error>> {
+        let path = CString::new(path.as_os_str().as_bytes())?;
+
+        let time = libc::timeval {
+            tv_sec: secs,
+            tv_usec: 0,
+        };
+
+        let times = [time, time];
+
+        assert_eq!(
+            unsafe { libc::utimes(path.as_ptr(), ptr::addr_of!(times[0])) },
+            0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn times() -> Result<(), Box<dyn Error>> {
+        let tmp = TempDir::new()?;
+        let file = tmp.path().join("file");
+
+        File::create(&file)?;
+        backdate(&file, MODIFIED)?;
+
+        let changed = fs::metadata(&file)?.ctime();
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+
+        let out = super::utils::run_cmd(&[
+            "--columns",
+            "time,size",
+            "--time",
+            "mod,change",
+            "--time-format",
+            "custom:%s",
+            &dir,
+        ]);
+
+        assert_eq!(
+            out.lines().next(),
+            Some(format!("{MODIFIED} {changed} 0 B ┌─ file").as_str())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ctime_is_create() {
+        let run = |time| {
+            super::utils::run_cmd(&[
+                "--columns",
+                "time,size",
+                "--time",
+                time,
+                "--time-format",
+                "custom:%s",
+                "tests/data",
+            ])
+        };
+
+        assert_eq!(run("ctime"), run("create"));
+    }
+
+    #[test]
+    fn custom_format_width() -> Result<(), Box<dyn Error>> {
+        let tmp = TempDir::new()?;
+
+        // Seconds since the epoch are a single digit for one file and ten digits for the other.
+        for (name, secs) in [("first", 1), ("second", MODIFIED)] {
+            let file = tmp.path().join(name);
+            File::create(&file)?;
+            backdate(&file, secs)?;
+        }
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+
+        let out = super::utils::run_cmd(&[
+            "--columns",
+            "time,size",
+            "--time",
+            "mod",
+            "--time-format",
+            "custom:%s",
+            "--sort",
+            "name",
+            "--layout",
+            "flat",
+            &dir,
+        ]);
+
+        let files = out
+            .lines()
+            .filter(|line| line.ends_with("first") || line.ends_with("second"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            files,
+            [
+                format!("{MODIFIED} 0 B   second"),
+                String::from("         1 0 B   first"),
+            ]
+        );
+
+        Ok(())
+    }
+}
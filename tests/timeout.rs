@@ -0,0 +1,100 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn timeout() {
+    assert_eq!(
+        utils::run_cmd(&["--timeout", "1m", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+            143 B ┌─ the_yellow_king
+            100 B ├─ nylarlathotep.txt
+            161 B ├─ nemesis.txt
+             83 B ├─ necronomicon.txt
+            446 B │  ┌─ lipsum.txt
+            446 B ├─ lipsum
+            308 B │  ┌─ polaris.txt
+            308 B ├─ dream_cycle
+           1241 B data
+
+           3 directories, 6 files"
+        )
+    );
+}
+
+#[test]
+fn timeout_elapsed() {
+    for scanner in ["walk", "getdents"] {
+        let out = utils::run_cmd(&["--scanner", scanner, "--timeout", "0s", "tests/data"]);
+
+        assert!(out.starts_with("data [incomplete]"), "{out}");
+        assert!(
+            out.ends_with(
+                "Traversal timed out after 0ns; totals and the sizes of incomplete directories are lower bounds"
+            ),
+            "{out}"
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn timeout_invalid() {
+    utils::run_cmd(&["--timeout", "10d", "tests/data"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn timeout_following_links() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{fs, os::unix::fs::symlink};
+    use tempfile::TempDir;
+
+    const DIRS: usize = 64;
+    const FILES: usize = 16;
+
+    let tmp = TempDir::new()?;
+
+    for d in 0..DIRS {
+        let dir = tmp.path().join(format!("dir{d:02}"));
+        fs::create_dir(&dir)?;
+        for f in 0..FILES {
+            symlink("missing", dir.join(format!("orphan{f:02}")))?;
+            fs::write(dir.join(format!("file{f:02}")), "")?;
+        }
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+
+    // Wherever traversal is cut short, directories that aren't flagged must be complete,
+    // including the broken symlinks that are recovered rather than followed.
+    for timeout in ["1ms", "2ms", "3ms", "5ms", "10ms"] {
+        let out = utils::run_cmd(&[
+            "--follow",
+            "--suppress-size",
+            "--layout",
+            "flat",
+            "--timeout",
+            timeout,
+            &dir,
+        ]);
+
+        for d in 0..DIRS {
+            let name = format!("dir{d:02}");
+
+            if !out.lines().any(|line| line.trim() == name) {
+                continue;
+            }
+
+            let prefix = format!("{name}/");
+            let entries = out
+                .lines()
+                .filter(|line| line.trim().starts_with(&prefix))
+                .count();
+
+            assert_eq!(entries, FILES * 2, "Expected {name} to be flagged: {out}");
+        }
+    }
+
+    Ok(())
+}
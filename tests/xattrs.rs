@@ -0,0 +1,52 @@
+mod utils;
+
+#[cfg(target_os = "linux")]
+mod test {
+    use std::{ffi::CString, fs::File, os::unix::ffi::OsStrExt};
+    use tempfile::TempDir;
+
+    #[test]
+    fn xattrs() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let file = tmp.path().join("file");
+        File::create(&file)?;
+
+        let path = CString::new(file.as_os_str().as_bytes())?;
+        let name = CString::new("user.comment")?;
+        let value = b"hello world";
+
+        let rc = unsafe {
+            libc::setxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr().cast(),
+                value.len(),
+                0,
+            )
+        };
+
+        // Not every filesystem supports user extended attributes.
+        if rc != 0 {
+            return Ok(());
+        }
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+
+        let out = super::utils::run_cmd(&["--suppress-size", "--xattrs", &dir]);
+
+        assert!(
+            out.starts_with("┌─ file {user.comment}"),
+            "Failed to list extended attributes: {out}"
+        );
+
+        let out =
+            super::utils::run_cmd(&["--suppress-size", "--xattrs", "--xattr-values", "5", &dir]);
+
+        assert!(
+            out.starts_with("┌─ file {user.comment=\"hello\u{2026}\"}"),
+            "Failed to show truncated values of extended attributes: {out}"
+        );
+
+        Ok(())
+    }
+}